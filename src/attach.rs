@@ -1,27 +1,126 @@
-use libc;
-use read_process_memory;
+use crate::error::Result;
 
-use libc::{c_int, c_long};
+/// Registers needed to unwind the native stack
+#[derive(Clone, Copy)]
+pub struct Registers {
+    pub ip: usize,
+    pub sp: usize,
+    /// Frame pointer, `rbp` or `x29`
+    pub bp: usize,
+    /// Link register `x30` on aarch64, holding the return address until the
+    /// function saves it. Always 0 on x86_64, where it is on the stack.
+    pub lr: usize,
+    /// Bits of return addresses holding aarch64 pointer authentication
+    /// codes, 0 when the process doesn't sign them
+    pub pac_mask: usize,
+}
 
 #[cfg(target_os = "linux")]
-extern {
-    // addr and data should be c_void?
-    fn ptrace(request: c_int, pid: libc::pid_t, addr: usize, data: usize) -> c_long;
+fn null() -> *mut libc::c_void
+{
+    std::ptr::null_mut()
 }
 
+/// Stop the process, and wait until it actually is stopped so that its
+/// memory doesn't change while it is read
 #[cfg(target_os = "linux")]
-pub fn attach(pid: read_process_memory::Pid) {
+pub fn attach(pid: read_process_memory::Pid) -> Result<()> {
     unsafe {
-        ptrace(0x4206, pid, 0, 0); // PTRACE_SEIZE
-        ptrace(0x4207, pid, 0, 0); // PTRACE_INTERRUPT
+        if libc::ptrace(libc::PTRACE_SEIZE, pid, null(), null()) != 0 {
+            return Err(crate::error::Error::Attach(std::io::Error::last_os_error()));
+        }
+        libc::ptrace(libc::PTRACE_INTERRUPT, pid, null(), null());
+        let mut status = 0;
+        if libc::waitpid(pid, &mut status, libc::__WALL) != pid {
+            let e = std::io::Error::last_os_error();
+            detach(pid);
+            return Err(crate::error::Error::Attach(e));
+        }
     }
+    Ok(())
 }
 
 #[cfg(target_os = "linux")]
 pub fn detach(pid: read_process_memory::Pid) {
     unsafe {
-        ptrace(17, pid, 0, 0); // PTRACE_DETACH
+        libc::ptrace(libc::PTRACE_DETACH, pid, null(), null());
+    }
+}
+
+/// Registers of the attached process
+#[cfg(target_os = "linux")]
+pub fn get_registers(pid: read_process_memory::Pid) -> Option<Registers> {
+    unsafe { read_registers(pid) }
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+unsafe fn read_registers(pid: read_process_memory::Pid) -> Option<Registers> {
+    let mut regs: libc::user_regs_struct = std::mem::zeroed();
+    if libc::ptrace(libc::PTRACE_GETREGS, pid, null(), &mut regs as *mut _ as *mut libc::c_void) != 0 {
+        return None;
+    }
+    Some(Registers { ip: regs.rip as usize, sp: regs.rsp as usize, bp: regs.rbp as usize, lr: 0, pac_mask: 0 })
+}
+
+/// `struct user_pt_regs` of `asm/ptrace.h`
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+#[repr(C)]
+struct UserPtRegs {
+    regs: [u64; 31],
+    sp: u64,
+    pc: u64,
+    pstate: u64,
+}
+
+/// aarch64 has no `PTRACE_GETREGS`, the general purpose registers are the
+/// `NT_PRSTATUS` register set
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+unsafe fn read_registers(pid: read_process_memory::Pid) -> Option<Registers> {
+    let mut regs: UserPtRegs = std::mem::zeroed();
+    let mut iov = libc::iovec {
+        iov_base: &mut regs as *mut _ as *mut libc::c_void,
+        iov_len: std::mem::size_of::<UserPtRegs>(),
+    };
+    let nt_prstatus: usize = 1; // elf.h
+    if libc::ptrace(libc::PTRACE_GETREGSET, pid, nt_prstatus, &mut iov as *mut libc::iovec) != 0 {
+        return None;
+    }
+    Some(Registers {
+        ip: regs.pc as usize,
+        sp: regs.sp as usize,
+        bp: regs.regs[29] as usize,
+        lr: regs.regs[30] as usize,
+        pac_mask: read_pac_mask(pid),
+    })
+}
+
+/// `struct user_pac_mask` of `asm/ptrace.h`
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+#[repr(C)]
+struct UserPacMask {
+    data_mask: u64,
+    insn_mask: u64,
+}
+
+/// Mask of the authentication code in signed code addresses, missing on
+/// kernels or CPUs without pointer authentication
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+unsafe fn read_pac_mask(pid: read_process_memory::Pid) -> usize {
+    let mut mask = UserPacMask { data_mask: 0, insn_mask: 0 };
+    let mut iov = libc::iovec {
+        iov_base: &mut mask as *mut _ as *mut libc::c_void,
+        iov_len: std::mem::size_of::<UserPacMask>(),
+    };
+    let nt_arm_pac_mask: usize = 0x406; // elf.h
+    if libc::ptrace(libc::PTRACE_GETREGSET, pid, nt_arm_pac_mask, &mut iov as *mut libc::iovec) != 0 {
+        return 0;
     }
+    mask.insn_mask as usize
+}
+
+#[cfg(all(target_os = "linux", not(any(target_arch = "x86_64", target_arch = "aarch64"))))]
+unsafe fn read_registers(_: read_process_memory::Pid) -> Option<Registers> {
+    None
 }
 
 #[cfg(not(target_os = "linux"))]
@@ -32,6 +131,12 @@ pub fn detach(_: read_process_memory::Pid) {
 
 #[cfg(not(target_os = "linux"))]
 #[allow(dead_code)]
-pub fn attach(_: read_process_memory::Pid) {
+pub fn attach(_: read_process_memory::Pid) -> Result<()> {
+    Ok(())
+}
 
+#[cfg(not(target_os = "linux"))]
+#[allow(dead_code)]
+pub fn get_registers(_: read_process_memory::Pid) -> Option<Registers> {
+    None
 }
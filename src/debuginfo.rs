@@ -1,62 +1,303 @@
-use std;
+use std::collections::HashMap;
 use std::fs;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::process;
-use std::process::{Command, Stdio};
-use regex::Regex;
+use std::io;
 use read_process_memory::*;
+use object::{Architecture, Object, ObjectSection, ObjectSymbol, ReadCache, ReadRef};
 
-pub fn get_executor_globals_address<Pid>(pid: Pid) -> usize
+use crate::dwarf::{self, StructLayout};
+use crate::error::{Error, Result};
+use crate::export::hex;
+use crate::layout::Abi;
+use crate::procfs;
+use crate::symbol_cache;
+
+/// Address of `executor_globals`. A cached offset is only used when `valid`
+/// accepts it, it could be left from a wrong lookup.
+pub fn get_executor_globals_address<Pid, F>(pid: Pid, valid: F) -> Result<usize>
+where
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
+    F: Fn(usize) -> bool,
+{
+    resolve_symbol(pid, "executor_globals", valid)
+}
+
+/// Address of `sapi_globals`, missing from executables without a SAPI
+pub fn get_sapi_globals_address<Pid>(pid: Pid) -> Result<usize>
+where
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
+{
+    get_symbol_address(pid, "sapi_globals")
+}
+
+/// Address of a bss symbol of the executable in the process. Offsets are
+/// cached by build-id, looking them up reads the whole executable.
+pub fn get_symbol_address<Pid>(pid: Pid, symbol: &str) -> Result<usize>
 where
     Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
 {
-    get_maps_address(pid) + get_nm_address(pid)
+    resolve_symbol(pid, symbol, |_| true)
 }
 
-fn get_nm_address<Pid>(pid: Pid) -> usize
+fn resolve_symbol<Pid, F>(pid: Pid, symbol: &str, valid: F) -> Result<usize>
 where
-    Pid: TryIntoProcessHandle + std::fmt::Display,
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
+    F: Fn(usize) -> bool,
 {
-    let nm_command = Command::new("nm")
-        .arg("-D")
-        .arg(format!("/proc/{}/exe", pid))
-        .stdout(Stdio::piped())
-        .stdin(Stdio::null())
-        .stderr(Stdio::piped())
-        .output()
-        .unwrap_or_else(|e| panic!("failed to execute process: {}", e));
-    if !nm_command.status.success() {
-        panic!(
-            "failed to execute process: {}",
-            String::from_utf8(nm_command.stderr).unwrap()
-        )
+    let base = get_maps_address(pid)?;
+    let build_id = get_build_id(pid);
+    if let Some(build_id) = &build_id {
+        match symbol_cache::lookup(build_id, symbol) {
+            Some(offset) if valid(base + offset) => return Ok(base + offset),
+            Some(_) => symbol_cache::remove(build_id, symbol),
+            None => {}
+        }
     }
 
-    let nm_output = String::from_utf8(nm_command.stdout).unwrap();
-    let re = Regex::new(r"(\w+) [B] executor_globals").unwrap();
-    let cap = re.captures(&nm_output).unwrap_or_else(|| {
-        println!("Cannot find executor_globals in php process");
-        process::exit(1)
-    });
-    let address_str = cap.get(1).unwrap().as_str();
-    usize::from_str_radix(address_str, 16).unwrap()
+    let offset = get_symbol_offset(pid, symbol)?.ok_or_else(|| Error::Symbol(symbol.to_string()))?;
+    if let Some(build_id) = &build_id {
+        symbol_cache::store(build_id, symbol, offset);
+    }
+    Ok(base + offset)
 }
 
-fn get_maps_address<Pid>(pid: Pid) -> usize
+/// Offset of a symbol in the executable, from its own symbol tables, then
+/// from separate debug files and from its MiniDebugInfo
+fn get_symbol_offset<Pid>(pid: Pid, symbol: &str) -> Result<Option<usize>>
 where
-    Pid: TryIntoProcessHandle + std::fmt::Display,
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
 {
-    let map_path = format!("/proc/{}/maps", pid);
-    let exe_path = fs::read_link(format!("/proc/{}/exe", pid)).unwrap().to_string_lossy().to_string();
+    let data = fs::read(format!("/proc/{}/exe", pid))?;
+    let exe = parse_elf(&data)?;
+    if let Some(offset) = find_symbol(&exe, symbol) {
+        return Ok(Some(offset));
+    }
 
-    let file = File::open(map_path).unwrap();
-    for line in io::BufReader::new(file).lines() {
-        let line = line.unwrap();
-        if line.contains(&exe_path) {
-	    let address_str = line.split("-").collect::<Vec<&str>>()[0];
-            return usize::from_str_radix(address_str, 16).unwrap();
+    let debug_data = debug_file_paths(pid, &exe)
+        .into_iter()
+        .filter_map(|path| fs::read(path).ok())
+        .chain(mini_debug_info(&exe));
+    for data in debug_data {
+        if let Some(offset) = parse_elf(&data).ok().and_then(|debug| find_symbol(&debug, symbol)) {
+            return Ok(Some(offset));
         }
     }
-    0
+    Ok(None)
+}
+
+fn parse_elf(data: &[u8]) -> Result<object::File<'_>>
+{
+    object::File::parse(data).map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+fn find_symbol(file: &object::File, symbol: &str) -> Option<usize>
+{
+    file.symbols()
+        .chain(file.dynamic_symbols())
+        .find(|sym| sym.name() == Ok(symbol) && !sym.is_undefined() && sym.address() != 0)
+        .map(|sym| sym.address() as usize)
+}
+
+/// Layouts of the named structs from the DWARF of the executable, or of its
+/// separate debug file. They are cached by build-id, like symbols, reading
+/// DWARF takes longer than the rest of attaching.
+pub fn get_debug_structs<Pid>(pid: Pid, names: &[&str]) -> HashMap<String, StructLayout>
+where
+    Pid: std::fmt::Display + Copy,
+{
+    let build_id = match get_build_id(pid) {
+        Some(build_id) => build_id,
+        None => return read_debug_structs(pid, names),
+    };
+    if let Some(structs) = symbol_cache::lookup_structs(&build_id, names) {
+        return structs;
+    }
+    let structs = read_debug_structs(pid, names);
+    // Without debug info there is little to read, and a debug package
+    // installed later is picked up
+    if !structs.is_empty() {
+        symbol_cache::store_structs(&build_id, names, &structs);
+    }
+    structs
+}
+
+fn read_debug_structs<Pid>(pid: Pid, names: &[&str]) -> HashMap<String, StructLayout>
+where
+    Pid: std::fmt::Display + Copy,
+{
+    let exe = match fs::File::open(format!("/proc/{}/exe", pid)) {
+        Ok(exe) => ReadCache::new(exe),
+        Err(_) => return HashMap::new(),
+    };
+    let exe = match object::File::parse(&exe) {
+        Ok(exe) => exe,
+        Err(_) => return HashMap::new(),
+    };
+    if exe.section_by_name(".debug_info").is_some() {
+        return dwarf::read_structs(&exe, names);
+    }
+
+    for path in debug_file_paths(pid, &exe) {
+        let debug = match fs::File::open(path) {
+            Ok(debug) => ReadCache::new(debug),
+            Err(_) => continue,
+        };
+        if let Ok(debug) = object::File::parse(&debug) {
+            return dwarf::read_structs(&debug, names);
+        }
+    }
+    HashMap::new()
+}
+
+/// Where gdb looks for separate debug files: by build-id, then by the name
+/// in `.gnu_debuglink`. Debug packages are installed along with the binary,
+/// so inside its container first.
+fn debug_file_paths<'data, Pid, R>(pid: Pid, exe: &object::File<'data, R>) -> Vec<String>
+where
+    Pid: std::fmt::Display,
+    R: ReadRef<'data>,
+{
+    let mut paths = Vec::new();
+    if let Ok(Some(id)) = exe.build_id() {
+        if id.len() > 1 {
+            paths.push(format!("/usr/lib/debug/.build-id/{}/{}.debug", hex(&id[..1]), hex(&id[1..])));
+        }
+    }
+    if let (Ok(Some((name, _))), Ok(exe_path)) = (exe.gnu_debuglink(), fs::read_link(format!("/proc/{}/exe", pid))) {
+        let name = String::from_utf8_lossy(name);
+        let dir = exe_path.parent().map_or(String::new(), |dir| dir.to_string_lossy().to_string());
+        paths.push(format!("{}/{}", dir, name));
+        paths.push(format!("{}/.debug/{}", dir, name));
+        paths.push(format!("/usr/lib/debug{}/{}", dir, name));
+    }
+
+    paths
+        .into_iter()
+        .flat_map(|path| vec![format!("/proc/{}/root{}", pid, path), path])
+        .collect()
+}
+
+/// ELF with the symbol table kept by distributions stripping their binaries,
+/// xz-compressed in `.gnu_debugdata`
+fn mini_debug_info(exe: &object::File) -> Option<Vec<u8>>
+{
+    let compressed = exe.section_by_name(".gnu_debugdata")?.data().ok()?;
+    let mut data = Vec::new();
+    lzma_rs::xz_decompress(&mut &compressed[..], &mut data).ok()?;
+    Some(data)
+}
+
+/// Address range of the `.bss` section of the executable in the process
+pub fn get_bss_range<Pid>(pid: Pid) -> Result<(usize, usize)>
+where
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
+{
+    let data = fs::read(format!("/proc/{}/exe", pid))?;
+    let exe = parse_elf(&data)?;
+    let bss = exe.section_by_name(".bss").ok_or_else(|| Error::Symbol(".bss".to_string()))?;
+    let start = get_maps_address(pid)? + bss.address() as usize;
+    Ok((start, start + bss.size() as usize))
+}
+
+/// Load address of the executable. Its mappings are found by inode, the path
+/// in the maps is relative to the root of the process and may not exist here.
+fn get_maps_address<Pid>(pid: Pid) -> Result<usize>
+where
+    Pid: TryIntoProcessHandle + std::fmt::Display + Copy,
+{
+    let inode = procfs::exe_inode(pid)?;
+    Ok(procfs::read_maps(pid)?
+        .iter()
+        .find(|entry| entry.inode == inode && entry.path.is_some())
+        .map_or(0, |entry| entry.start))
+}
+
+/// Version of PHP the process runs, from the `X-Powered-By` header compiled
+/// into the executable, e.g. `7.3.12`. Cached by build-id, like symbols, as
+/// finding the header reads the whole executable.
+pub fn get_php_version<Pid>(pid: Pid) -> Result<String>
+where
+    Pid: std::fmt::Display,
+{
+    let exe = format!("/proc/{}/exe", pid);
+    match get_build_id(&pid) {
+        Some(build_id) => cached_php_version(&build_id, || read_php_version(&exe)),
+        None => read_php_version(&exe),
+    }
+}
+
+fn cached_php_version<F>(build_id: &[u8], read: F) -> Result<String>
+where
+    F: FnOnce() -> Result<String>,
+{
+    if let Some(version) = symbol_cache::lookup_version(build_id) {
+        return Ok(version);
+    }
+    let version = read()?;
+    symbol_cache::store_version(build_id, &version);
+    Ok(version)
+}
+
+fn read_php_version(exe: &str) -> Result<String>
+{
+    const HEADER: &[u8] = b"X-Powered-By: PHP/";
+
+    let data = fs::read(exe)?;
+    let start = data
+        .windows(HEADER.len())
+        .position(|window| window == HEADER)
+        .ok_or_else(|| Error::Symbol("PHP version".to_string()))?
+        + HEADER.len();
+    let version: String = data[start..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit() || **byte == b'.')
+        .map(|byte| *byte as char)
+        .collect();
+    Ok(version)
+}
+
+/// GNU build-id of the process executable, reading only the headers and
+/// notes
+pub fn get_build_id<Pid>(pid: Pid) -> Option<Vec<u8>>
+where
+    Pid: std::fmt::Display,
+{
+    let data = ReadCache::new(fs::File::open(format!("/proc/{}/exe", pid)).ok()?);
+    let file = object::File::parse(&data).ok()?;
+    file.build_id().ok()?.map(|id| id.to_vec())
+}
+
+/// Data model of the process, told by the ELF class and machine of its
+/// executable
+pub fn get_abi<Pid>(pid: Pid) -> Result<Abi>
+where
+    Pid: std::fmt::Display,
+{
+    let data = ReadCache::new(fs::File::open(format!("/proc/{}/exe", pid))?);
+    let file = object::File::parse(&data).map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+    Ok(match file.architecture() {
+        _ if file.is_64() => Abi::Lp64,
+        Architecture::Arm => Abi::Ilp32Arm,
+        _ => Abi::Ilp32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_version_skips_the_scan()
+    {
+        let dir = std::env::temp_dir().join(format!("php-stacktrace-cache-{}", std::process::id()));
+        std::env::set_var("XDG_CACHE_HOME", &dir);
+        let build_id = [0xab, 0xcd];
+
+        assert_eq!(cached_php_version(&build_id, || Ok("7.3.12".to_string())).unwrap(), "7.3.12");
+        let cached = cached_php_version(&build_id, || panic!("the executable is read again"));
+        assert_eq!(cached.unwrap(), "7.3.12");
+        // Another executable is read
+        assert_eq!(cached_php_version(&[0xef], || Ok("5.6.40".to_string())).unwrap(), "5.6.40");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
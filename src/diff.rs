@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+use clap::ArgMatches;
+
+use php_stacktrace::export::count_stacks;
+use php_stacktrace::recording::{Recording, MAGIC};
+
+use crate::{arg_value, fail};
+
+/// Folded stacks, `root;...;leaf` to sample count
+type Stacks = BTreeMap<String, usize>;
+
+/// Load a recording, or a file of folded stacks
+fn load(path: &str) -> io::Result<Stacks>
+{
+    let bytes = fs::read(path)?;
+    if bytes.starts_with(MAGIC) {
+        let recording = Recording::read(&bytes[..])?;
+        return Ok(count_stacks(&recording)
+            .into_iter()
+            .map(|(stack, count)| (stack.join(";"), count))
+            .collect());
+    }
+
+    let text = String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut stacks = Stacks::new();
+    for line in text.lines() {
+        let (stack, count) = match line.rsplit_once(' ') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        if let Ok(count) = count.parse::<usize>() {
+            *stacks.entry(stack.to_string()).or_insert(0) += count;
+        }
+    }
+    Ok(stacks)
+}
+
+/// `stack before after` lines, as read by flamegraph.pl for differential
+/// flame graphs
+fn write_folded(before: &Stacks, after: &Stacks, out: &mut dyn Write) -> io::Result<()>
+{
+    let stacks: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for stack in stacks {
+        let count_before = before.get(stack).copied().unwrap_or(0);
+        let count_after = after.get(stack).copied().unwrap_or(0);
+        writeln!(out, "{} {} {}", stack, count_before, count_after)?;
+    }
+    Ok(())
+}
+
+#[derive(Default, Clone, Copy)]
+struct Share {
+    leaf: f64,
+    inclusive: f64,
+}
+
+/// Share of samples each function is the leaf of, or anywhere on the stack
+fn function_shares(stacks: &Stacks) -> HashMap<&str, Share>
+{
+    let total = stacks.values().sum::<usize>().max(1) as f64;
+    let mut shares: HashMap<&str, Share> = HashMap::new();
+
+    for (stack, count) in stacks {
+        let share = *count as f64 / total;
+        let frames: Vec<&str> = stack.split(';').collect();
+        if let Some(leaf) = frames.last() {
+            shares.entry(leaf).or_default().leaf += share;
+        }
+        for function in frames.into_iter().collect::<BTreeSet<_>>() {
+            shares.entry(function).or_default().inclusive += share;
+        }
+    }
+    shares
+}
+
+/// Functions ranked by how much their inclusive share changed
+fn write_table(before: &Stacks, after: &Stacks, lines: usize, out: &mut dyn Write) -> io::Result<()>
+{
+    let shares_before = function_shares(before);
+    let shares_after = function_shares(after);
+
+    let functions: BTreeSet<&str> = shares_before.keys().chain(shares_after.keys()).copied().collect();
+    let mut rows: Vec<(&str, Share, Share)> = functions
+        .into_iter()
+        .map(|function| {
+            let before = shares_before.get(function).copied().unwrap_or_default();
+            let after = shares_after.get(function).copied().unwrap_or_default();
+            (function, before, after)
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        let delta_a = (a.2.inclusive - a.1.inclusive).abs();
+        let delta_b = (b.2.inclusive - b.1.inclusive).abs();
+        delta_b.partial_cmp(&delta_a).unwrap()
+    });
+
+    writeln!(
+        out,
+        "{} samples before, {} after",
+        before.values().sum::<usize>(),
+        after.values().sum::<usize>()
+    )?;
+    writeln!(out)?;
+    writeln!(out, "{:>7} {:>7} {:>8} {:>8}  FUNCTION", "BEFORE", "AFTER", "INCL", "LEAF")?;
+    for (function, before, after) in rows.iter().take(lines) {
+        writeln!(
+            out,
+            "{:>6.1}% {:>6.1}% {:>+7.1}% {:>+7.1}%  {}",
+            before.inclusive * 100.0,
+            after.inclusive * 100.0,
+            (after.inclusive - before.inclusive) * 100.0,
+            (after.leaf - before.leaf) * 100.0,
+            function
+        )?;
+    }
+    Ok(())
+}
+
+/// Compare two recordings or folded files
+pub fn run(matches: &ArgMatches)
+{
+    let load_or_exit = |path: &str| load(path).unwrap_or_else(|e| fail(format!("cannot read {}: {}", path, e)));
+    let before = load_or_exit(matches.value_of("BEFORE").unwrap());
+    let after = load_or_exit(matches.value_of("AFTER").unwrap());
+    let lines: usize = arg_value(matches, "lines");
+
+    let write = |out: &mut dyn Write| match matches.value_of("format").unwrap() {
+        "folded" => write_folded(&before, &after, out),
+        _ => write_table(&before, &after, lines, out),
+    };
+
+    let result = match matches.value_of("output") {
+        Some(output) => {
+            let file = File::create(output).unwrap_or_else(|e| fail(format!("cannot create {}: {}", output, e)));
+            let mut out = BufWriter::new(file);
+            write(&mut out).and_then(|_| out.flush())
+        }
+        None => write(&mut io::stdout().lock()),
+    };
+    result.unwrap_or_else(|e| fail(format!("cannot write {}: {}", matches.value_of("output").unwrap_or("stdout"), e)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use php_stacktrace::recording::RecordingWriter;
+    use php_stacktrace::{Frame, Trace};
+
+    fn temp_file(name: &str, contents: &[u8]) -> String
+    {
+        let path = std::env::temp_dir().join(format!("php-stacktrace-diff-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn stacks(lines: &[(&str, usize)]) -> Stacks
+    {
+        lines.iter().map(|(stack, count)| (stack.to_string(), *count)).collect()
+    }
+
+    #[test]
+    fn load_recording()
+    {
+        let path = temp_file("recording", b"");
+        let mut writer = RecordingWriter::new(File::create(&path).unwrap(), "7.3.12", 0, 10_000).unwrap();
+        let frame = Frame { function: Some("usleep".to_string()), ..Frame::default() };
+        let trace = Trace { frames: vec![frame, Frame::default()], coroutines: Vec::new(), native: Vec::new() };
+        writer.add_trace(0, 42, &trace).unwrap();
+        writer.add_trace(10_000, 42, &trace).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(load(&path).unwrap(), stacks(&[("main();usleep()", 2)]));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_folded()
+    {
+        let path = temp_file("folded", b"main;a 2\nmain;b 1\nno count\nmain;a 3\nmain;c x\n");
+        assert_eq!(load(&path).unwrap(), stacks(&[("main;a", 5), ("main;b", 1)]));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn load_reports_a_broken_recording()
+    {
+        let mut contents = MAGIC.to_vec();
+        contents.push(99);
+        let path = temp_file("broken", &contents);
+        let error = load(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("format version"), "{}", error);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn shares()
+    {
+        let stacks = stacks(&[("main;a;b", 1), ("main;a", 2), ("main;b;b", 1)]);
+        let shares = function_shares(&stacks);
+        let share = |function| (shares[function].leaf, shares[function].inclusive);
+        assert_eq!(share("main"), (0.0, 1.0));
+        assert_eq!(share("a"), (0.5, 0.75));
+        // Counted once in a recursive stack
+        assert_eq!(share("b"), (0.5, 0.5));
+        assert!(function_shares(&Stacks::new()).is_empty());
+    }
+
+    #[test]
+    fn folded_pairs()
+    {
+        let mut out = Vec::new();
+        write_folded(&stacks(&[("main;a", 2), ("main;b", 1)]), &stacks(&[("main;b", 4), ("main;c", 1)]), &mut out)
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "main;a 2 0\nmain;b 1 4\nmain;c 0 1\n");
+    }
+}
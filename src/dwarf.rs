@@ -0,0 +1,182 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use gimli::{AttributeValue, EndianSlice, RunTimeEndian, Unit, UnitOffset};
+use object::{Object, ObjectSection, ReadRef};
+
+/// Members of embedded structs and unions are flattened up to this depth
+const MAX_NESTING: usize = 3;
+
+/// Size and member offsets of a struct or union. Members of embedded structs
+/// are named by their path, e.g. `common.function_name`.
+pub struct StructLayout {
+    pub size: usize,
+    pub members: HashMap<String, usize>,
+}
+
+impl StructLayout {
+    pub fn offset(&self, member: &str) -> Option<usize>
+    {
+        self.members.get(member).copied()
+    }
+}
+
+type Slice<'a> = EndianSlice<'a, RunTimeEndian>;
+
+/// Layouts of the named structs and unions (`_zend_execute_data`, ...) from
+/// the DWARF of an ELF file, empty without debug info
+pub fn read_structs<'data, R>(file: &object::File<'data, R>, names: &[&str]) -> HashMap<String, StructLayout>
+where
+    R: ReadRef<'data>,
+{
+    let endian = if file.is_little_endian() { RunTimeEndian::Little } else { RunTimeEndian::Big };
+    let load = |id: gimli::SectionId| -> Result<Cow<[u8]>, gimli::Error> {
+        Ok(file
+            .section_by_name(id.name())
+            .and_then(|section| section.uncompressed_data().ok())
+            .unwrap_or(Cow::Borrowed(&[])))
+    };
+    let sections = match gimli::DwarfSections::load(load) {
+        Ok(sections) => sections,
+        Err(_) => return HashMap::new(),
+    };
+    let dwarf = sections.borrow(|section| EndianSlice::new(section, endian));
+
+    let mut structs = HashMap::new();
+    let mut units = dwarf.units();
+    while let Ok(Some(header)) = units.next() {
+        let unit = match dwarf.unit(header) {
+            Ok(unit) => unit,
+            Err(_) => continue,
+        };
+        let mut entries = unit.entries();
+        while let Ok(Some((_, entry))) = entries.next_dfs() {
+            if !is_aggregate(entry.tag()) || entry.attr(gimli::DW_AT_declaration).ok().flatten().is_some() {
+                continue;
+            }
+            let name = match entry.attr_value(gimli::DW_AT_name) {
+                Ok(Some(name)) => match dwarf.attr_string(&unit, name) {
+                    Ok(name) => name.to_string_lossy().into_owned(),
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            if !names.contains(&name.as_str()) || structs.contains_key(&name) {
+                continue;
+            }
+            if let Some(layout) = read_struct(&dwarf, &unit, entry.offset()) {
+                structs.insert(name, layout);
+            }
+        }
+        if structs.len() == names.len() {
+            break;
+        }
+    }
+    structs
+}
+
+fn is_aggregate(tag: gimli::DwTag) -> bool
+{
+    tag == gimli::DW_TAG_structure_type || tag == gimli::DW_TAG_union_type
+}
+
+fn read_struct(dwarf: &gimli::Dwarf<Slice>, unit: &Unit<Slice>, offset: UnitOffset) -> Option<StructLayout>
+{
+    let size = unit.entry(offset).ok()?.attr_value(gimli::DW_AT_byte_size).ok()??.udata_value()? as usize;
+    let mut members = HashMap::new();
+    read_members(dwarf, unit, offset, "", 0, 0, &mut members);
+    Some(StructLayout { size, members })
+}
+
+/// Members of the struct at `offset`, placed at `base` in the outer struct
+fn read_members(
+    dwarf: &gimli::Dwarf<Slice>,
+    unit: &Unit<Slice>,
+    offset: UnitOffset,
+    prefix: &str,
+    base: usize,
+    depth: usize,
+    members: &mut HashMap<String, usize>,
+)
+{
+    let mut fields = Vec::new();
+    if let Ok(mut tree) = unit.entries_tree(Some(offset)) {
+        if let Ok(root) = tree.root() {
+            let mut children = root.children();
+            while let Ok(Some(child)) = children.next() {
+                let entry = child.entry();
+                if entry.tag() != gimli::DW_TAG_member {
+                    continue;
+                }
+                let name = entry
+                    .attr_value(gimli::DW_AT_name)
+                    .ok()
+                    .flatten()
+                    .and_then(|name| dwarf.attr_string(unit, name).ok())
+                    .map(|name| name.to_string_lossy().into_owned());
+                // Union members have no location
+                let location = match entry.attr_value(gimli::DW_AT_data_member_location) {
+                    Ok(Some(value)) => member_location(value, unit.encoding()),
+                    _ => Some(0),
+                };
+                let member_type = match entry.attr_value(gimli::DW_AT_type) {
+                    Ok(Some(AttributeValue::UnitRef(member_type))) => Some(member_type),
+                    _ => None,
+                };
+                if let Some(location) = location {
+                    fields.push((name, base + location, member_type));
+                }
+            }
+        }
+    }
+
+    for (name, location, member_type) in fields {
+        let path = match &name {
+            Some(name) => format!("{}{}", prefix, name),
+            // Anonymous struct or union, its members belong to the outer one
+            None => prefix.trim_end_matches('.').to_string(),
+        };
+        if name.is_some() {
+            members.insert(path.clone(), location);
+        }
+        if depth < MAX_NESTING {
+            if let Some(inner) = member_type.and_then(|member_type| resolve_aggregate(unit, member_type)) {
+                let prefix = if path.is_empty() { String::new() } else { format!("{}.", path) };
+                read_members(dwarf, unit, inner, &prefix, location, depth + 1, members);
+            }
+        }
+    }
+}
+
+/// The struct or union a member's type names, through typedefs and
+/// qualifiers; `None` for pointers and scalars
+fn resolve_aggregate(unit: &Unit<Slice>, mut offset: UnitOffset) -> Option<UnitOffset>
+{
+    loop {
+        let entry = unit.entry(offset).ok()?;
+        match entry.tag() {
+            tag if is_aggregate(tag) => return Some(offset),
+            gimli::DW_TAG_typedef | gimli::DW_TAG_const_type | gimli::DW_TAG_volatile_type => {
+                match entry.attr_value(gimli::DW_AT_type).ok()?? {
+                    AttributeValue::UnitRef(next) => offset = next,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// `DW_AT_data_member_location`, a constant or, in older DWARF, a
+/// `DW_OP_plus_uconst` expression
+fn member_location(value: AttributeValue<Slice>, encoding: gimli::Encoding) -> Option<usize>
+{
+    if let Some(location) = value.udata_value() {
+        return Some(location as usize);
+    }
+    let mut operations = value.exprloc_value()?.operations(encoding);
+    match operations.next().ok()?? {
+        gimli::Operation::PlusConstant { value } => Some(value as usize),
+        _ => None,
+    }
+}
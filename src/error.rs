@@ -0,0 +1,51 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the memory of the process failed, usually because it exited
+    Memory(usize, io::Error),
+    /// The process couldn't be stopped with ptrace
+    Attach(io::Error),
+    /// A symbol wasn't found in the PHP executable
+    Symbol(String),
+    /// The PHP version couldn't be detected, or isn't supported
+    Version(String),
+    /// A structure layout doesn't match what was read
+    Layout(String),
+    Io(io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Error::Memory(addr, e) => write!(f, "cannot read memory at {:#x}: {}", addr, e),
+            Error::Attach(e) => write!(f, "cannot attach: {}", e),
+            Error::Symbol(symbol) => write!(f, "cannot find {} in php process", symbol),
+            Error::Version(version) => write!(f, "unsupported PHP version {}", version),
+            Error::Layout(e) => write!(f, "invalid layout: {}", e),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)>
+    {
+        match self {
+            Error::Memory(_, e) | Error::Attach(e) | Error::Io(e) => Some(e),
+            Error::Symbol(_) | Error::Version(_) | Error::Layout(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error
+    {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use crate::process_reader::Trace;
+use crate::recording::{write_varint, Recording};
+
+pub fn write_text<W: Write>(recording: &Recording, out: &mut W) -> io::Result<()>
+{
+    for sample in &recording.samples {
+        writeln!(out, "# {:.6}s pid {}", sample.time as f64 / 1e6, sample.pid)?;
+        for frame in recording.frames(sample) {
+            writeln!(out, "{}", frame)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Stacks of the recording, root first, with the number of samples of each
+pub fn count_stacks(recording: &Recording) -> BTreeMap<Vec<&str>, usize>
+{
+    let mut stacks = BTreeMap::new();
+    for sample in &recording.samples {
+        let mut stack: Vec<&str> = recording.frames(sample).collect();
+        stack.reverse();
+        *stacks.entry(stack).or_insert(0) += 1;
+    }
+    stacks
+}
+
+/// One line per distinct stack, `root;...;leaf count`, as read by flamegraph.pl
+pub fn write_folded<W: Write>(recording: &Recording, out: &mut W) -> io::Result<()>
+{
+    for (stack, count) in count_stacks(recording) {
+        writeln!(out, "{} {}", stack.join(";"), count)?;
+    }
+    Ok(())
+}
+
+pub fn write_json<W: Write>(recording: &Recording, out: &mut W) -> io::Result<()>
+{
+    write!(out, "{{\"php_version\":{},", json_string(&recording.php_version))?;
+    write!(out, "\"start_time_us\":{},\"interval_us\":{},", recording.start_time, recording.interval)?;
+
+    write!(out, "\"processes\":[")?;
+    for (i, process) in recording.processes.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(out, "{{\"pid\":{},\"build_id\":", process.pid)?;
+        match &process.build_id {
+            Some(build_id) => write!(out, "\"{}\"}}", hex(build_id))?,
+            None => write!(out, "null}}")?,
+        }
+    }
+
+    write!(out, "],\"samples\":[")?;
+    for (i, sample) in recording.samples.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(out, "{{\"time_us\":{},\"pid\":{},\"frames\":[", sample.time, sample.pid)?;
+        for (j, frame) in recording.frames(sample).enumerate() {
+            if j > 0 {
+                write!(out, ",")?;
+            }
+            write!(out, "{}", json_string(frame))?;
+        }
+        write!(out, "]}}")?;
+    }
+    writeln!(out, "]}}")
+}
+
+/// Frames of a trace as a JSON array, top of the stack first
+pub fn write_trace_json<W: Write + ?Sized>(trace: &Trace, out: &mut W) -> io::Result<()>
+{
+    let json_option = |value: Option<String>| value.map_or("null".to_string(), |value| json_string(&value));
+
+    write!(out, "[")?;
+    for (i, frame) in trace.frames.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(
+            out,
+            "{{\"function\":{},\"file\":{},\"opcode\":{},\"blocked\":{}",
+            json_string(&frame.name()),
+            json_option(frame.file.clone()),
+            json_option(frame.opcode.map(str::to_string)),
+            json_option(frame.blocked.as_ref().map(ToString::to_string))
+        )?;
+        if let Some(args) = &frame.args {
+            let args: Vec<String> = args.iter().map(|arg| json_string(arg)).collect();
+            write!(out, ",\"args\":[{}]", args.join(","))?;
+        }
+        write!(out, "}}")?;
+    }
+    write!(out, "]")
+}
+
+pub fn json_string(value: &str) -> String
+{
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub fn hex(bytes: &[u8]) -> String
+{
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Uncompressed `perftools.profiles.Profile` protobuf, which `go tool pprof`
+/// reads as is
+pub fn write_pprof<W: Write>(recording: &Recording, out: &mut W) -> io::Result<()>
+{
+    // String 0 must be empty; each recording string gets a function and a
+    // location with the same id, offset by one as id 0 is reserved
+    let mut strings: Vec<&str> = vec!["", "samples", "count", "wall", "nanoseconds"];
+    let string_base = strings.len() as u64;
+    strings.extend(recording.strings.iter().map(|s| s.as_str()));
+
+    let mut profile = Vec::new();
+
+    // sample_type
+    let mut value_type = Vec::new();
+    proto_varint(&mut value_type, 1, 1)?;
+    proto_varint(&mut value_type, 2, 2)?;
+    proto_bytes(&mut profile, 1, &value_type)?;
+
+    // sample, identical stacks merged
+    let mut stacks: HashMap<&[u32], u64> = HashMap::new();
+    for sample in &recording.samples {
+        *stacks.entry(&sample.stack).or_insert(0) += 1;
+    }
+    let mut stacks: Vec<(&[u32], u64)> = stacks.into_iter().collect();
+    stacks.sort();
+    for (stack, count) in stacks {
+        let mut sample = Vec::new();
+        let mut location_ids = Vec::new();
+        for id in stack {
+            write_varint(&mut location_ids, *id as u64 + 1)?;
+        }
+        proto_bytes(&mut sample, 1, &location_ids)?;
+        let mut values = Vec::new();
+        write_varint(&mut values, count)?;
+        proto_bytes(&mut sample, 2, &values)?;
+        proto_bytes(&mut profile, 2, &sample)?;
+    }
+
+    // location
+    for id in 0..recording.strings.len() as u64 {
+        let mut line = Vec::new();
+        proto_varint(&mut line, 1, id + 1)?;
+        let mut location = Vec::new();
+        proto_varint(&mut location, 1, id + 1)?;
+        proto_bytes(&mut location, 4, &line)?;
+        proto_bytes(&mut profile, 4, &location)?;
+    }
+
+    // function
+    for id in 0..recording.strings.len() as u64 {
+        let mut function = Vec::new();
+        proto_varint(&mut function, 1, id + 1)?;
+        proto_varint(&mut function, 2, string_base + id)?;
+        proto_varint(&mut function, 3, string_base + id)?;
+        proto_bytes(&mut profile, 5, &function)?;
+    }
+
+    // string_table
+    for string in strings {
+        proto_bytes(&mut profile, 6, string.as_bytes())?;
+    }
+
+    // time_nanos, duration_nanos
+    proto_varint(&mut profile, 9, recording.start_time * 1000)?;
+    let duration = recording.samples.last().map_or(0, |sample| sample.time);
+    proto_varint(&mut profile, 10, duration * 1000)?;
+
+    // period_type, period
+    let mut period_type = Vec::new();
+    proto_varint(&mut period_type, 1, 3)?;
+    proto_varint(&mut period_type, 2, 4)?;
+    proto_bytes(&mut profile, 11, &period_type)?;
+    proto_varint(&mut profile, 12, recording.interval * 1000)?;
+
+    out.write_all(&profile)
+}
+
+fn proto_varint(out: &mut Vec<u8>, field: u64, value: u64) -> io::Result<()>
+{
+    write_varint(out, field << 3)?;
+    write_varint(out, value)
+}
+
+fn proto_bytes(out: &mut Vec<u8>, field: u64, bytes: &[u8]) -> io::Result<()>
+{
+    write_varint(out, (field << 3) | 2)?;
+    write_varint(out, bytes.len() as u64)?;
+    out.write_all(bytes)
+}
+
+pub fn write<W: Write>(format: &str, recording: &Recording, out: &mut W) -> io::Result<()>
+{
+    match format {
+        "folded" => write_folded(recording, out),
+        "json" => write_json(recording, out),
+        "pprof" => write_pprof(recording, out),
+        _ => write_text(recording, out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recording
+    {
+        let mut recording = Recording::new("7.3.12", 1_000, 10_000);
+        for (time, names) in [(0, &["usleep", "main"][..]), (10_000, &["strlen", "main"]), (20_000, &["usleep", "main"])] {
+            recording.add_trace(time, 42, &Trace::of_functions(names));
+        }
+        recording
+    }
+
+    fn read_varint(bytes: &mut &[u8]) -> u64
+    {
+        let mut value = 0;
+        for shift in (0..).step_by(7) {
+            let byte = bytes[0];
+            *bytes = &bytes[1..];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        value
+    }
+
+    /// Packed repeated varints
+    fn read_varints(mut bytes: &[u8]) -> Vec<u64>
+    {
+        let mut values = Vec::new();
+        while !bytes.is_empty() {
+            values.push(read_varint(&mut bytes));
+        }
+        values
+    }
+
+    /// Fields of a protobuf message, by number, with either their value or
+    /// their bytes
+    fn proto_fields(mut bytes: &[u8]) -> Vec<(u64, Result<u64, &[u8]>)>
+    {
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes);
+            let value = match key & 7 {
+                0 => Ok(read_varint(&mut bytes)),
+                2 => {
+                    let len = read_varint(&mut bytes) as usize;
+                    let (value, rest) = bytes.split_at(len);
+                    bytes = rest;
+                    Err(value)
+                }
+                wire_type => panic!("unexpected wire type {}", wire_type),
+            };
+            fields.push((key >> 3, value));
+        }
+        fields
+    }
+
+    /// The id and another field of a message
+    fn id_and_field(message: &[u8], field: u64) -> (u64, Result<u64, &[u8]>)
+    {
+        let fields = proto_fields(message);
+        let get = |number| fields.iter().find(|(n, _)| *n == number).map(|(_, value)| *value).unwrap();
+        (get(1).unwrap(), get(field))
+    }
+
+    #[test]
+    fn folded()
+    {
+        let mut out = Vec::new();
+        write_folded(&recording(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "main();strlen() 1\nmain();usleep() 2\n");
+    }
+
+    #[test]
+    fn json()
+    {
+        let mut out = Vec::new();
+        write_json(&recording(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                r#"{"php_version":"7.3.12","start_time_us":1000,"interval_us":10000,"processes":[],"samples":["#,
+                r#"{"time_us":0,"pid":42,"frames":["usleep()","main()"]},"#,
+                r#"{"time_us":10000,"pid":42,"frames":["strlen()","main()"]},"#,
+                r#"{"time_us":20000,"pid":42,"frames":["usleep()","main()"]}]}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn pprof_round_trip()
+    {
+        let mut out = Vec::new();
+        write_pprof(&recording(), &mut out).unwrap();
+        let profile = proto_fields(&out);
+        let field = |number: u64| profile.iter().filter(move |(n, _)| *n == number).map(|(_, value)| *value);
+
+        let strings: Vec<&str> = field(6).map(|value| std::str::from_utf8(value.unwrap_err()).unwrap()).collect();
+        assert_eq!(strings[0], "");
+
+        // Function ids to names, and location ids to function ids
+        let functions: HashMap<u64, &str> = field(5)
+            .map(|function| id_and_field(function.unwrap_err(), 2))
+            .map(|(id, name)| (id, strings[name.unwrap() as usize]))
+            .collect();
+        let locations: HashMap<u64, u64> = field(4)
+            .map(|location| id_and_field(location.unwrap_err(), 4))
+            .map(|(id, line)| (id, id_and_field(line.unwrap_err(), 1).0))
+            .collect();
+
+        let mut stacks: Vec<(Vec<&str>, u64)> = field(2)
+            .map(|sample| {
+                let fields = proto_fields(sample.unwrap_err());
+                let stack = read_varints(fields[0].1.unwrap_err()).iter().map(|id| functions[&locations[id]]).collect();
+                (stack, read_varints(fields[1].1.unwrap_err())[0])
+            })
+            .collect();
+        stacks.sort();
+        assert_eq!(stacks, [(vec!["strlen()", "main()"], 1), (vec!["usleep()", "main()"], 2)]);
+
+        assert_eq!(field(9).next(), Some(Ok(1_000_000)));
+        assert_eq!(field(10).next(), Some(Ok(20_000_000)));
+        assert_eq!(field(12).next(), Some(Ok(10_000_000)));
+    }
+}
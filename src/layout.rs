@@ -0,0 +1,811 @@
+use std::collections::HashMap;
+use std::mem::{align_of, offset_of, size_of};
+
+use crate::dwarf::StructLayout;
+use crate::opcodes;
+use crate::sapi::{self, ilp32};
+use crate::{php56, php56_32, php72, php72_32, php73, php73_32};
+
+/// Sizes of the bundled structs on LP64 Linux. The bindings were generated
+/// on x86_64; aarch64 has the same type sizes and alignment, which this
+/// checks when building there.
+macro_rules! assert_sizes {
+    ($php:ident, $eg:expr, $ex:expr, $func:expr, $op:expr, $zval:expr, $ht:expr) => {
+        #[cfg(target_pointer_width = "64")]
+        const _: () = {
+            assert!(size_of::<$php::zend_executor_globals>() == $eg);
+            assert!(size_of::<$php::zend_execute_data>() == $ex);
+            assert!(size_of::<$php::zend_function>() == $func);
+            assert!(size_of::<$php::zend_op>() == $op);
+            assert!(size_of::<$php::zval>() == $zval);
+            assert!(size_of::<$php::HashTable>() == $ht);
+        };
+    };
+}
+
+assert_sizes!(php56, 1264, 136, 248, 48, 24, 72);
+assert_sizes!(php72, 1592, 80, 224, 32, 16, 56);
+assert_sizes!(php73, 1600, 72, 216, 32, 16, 56);
+assert_sizes!(php56_32, 696, 68, 148, 28, 16, 40);
+assert_sizes!(php72_32, 988, 44, 136, 28, 16, 44);
+assert_sizes!(php73_32, 1000, 44, 132, 28, 16, 44);
+
+/// `struct stat` of the host is 144 bytes on x86_64, 128 on aarch64
+#[cfg(target_arch = "x86_64")]
+const _: () = assert!(size_of::<sapi::sapi_globals_struct>() == 448);
+const _: () = assert!(size_of::<ilp32::sapi_globals_struct>() == 268);
+
+/// Data model of the traced process
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Abi {
+    /// x86_64 and aarch64
+    Lp64,
+    /// i686
+    Ilp32,
+    /// armv7, which aligns 8-byte types, and so zvals, to 8 bytes
+    Ilp32Arm,
+}
+
+impl Abi {
+    /// Size of a pointer, `long` and `size_t`
+    pub fn word(self) -> usize
+    {
+        match self {
+            Abi::Lp64 => 8,
+            Abi::Ilp32 | Abi::Ilp32Arm => 4,
+        }
+    }
+}
+
+/// Structs looked up in the DWARF of the executable
+pub const DWARF_STRUCTS: &[&str] = &[
+    "_zend_executor_globals",
+    "_zend_execute_data",
+    "_zend_function",
+    "_zend_string",
+    "_zend_class_entry",
+    "_zend_generator",
+    "_sapi_globals_struct",
+];
+
+pub struct ExecutorGlobalsLayout {
+    pub size: usize,
+    pub function_table: usize,
+    pub class_table: usize,
+    pub zend_constants: usize,
+    pub current_execute_data: usize,
+    pub precision: usize,
+    pub object_buckets: usize,
+    /// `objects_store.top`, one past the last handle in use
+    pub objects_top: usize,
+}
+
+impl ExecutorGlobalsLayout {
+    /// Move the fields read by `bytes`, for something inserted before them
+    fn shift(&mut self, bytes: usize)
+    {
+        for field in [
+            &mut self.size,
+            &mut self.function_table,
+            &mut self.class_table,
+            &mut self.zend_constants,
+            &mut self.current_execute_data,
+            &mut self.precision,
+            &mut self.object_buckets,
+            &mut self.objects_top,
+        ] {
+            *field += bytes;
+        }
+    }
+}
+
+pub struct ExecuteDataLayout {
+    pub size: usize,
+    pub opline: usize,
+    /// `func`, or `function_state.function` in PHP 5
+    pub func: usize,
+    pub prev_execute_data: usize,
+}
+
+pub struct FunctionLayout {
+    pub size: usize,
+    pub type_: usize,
+    pub function_name: usize,
+    pub scope: usize,
+    pub filename: usize,
+    pub line_start: usize,
+    pub num_args: usize,
+    pub last_var: usize,
+    pub t: usize,
+}
+
+pub struct ClassEntryLayout {
+    pub name: usize,
+}
+
+pub struct OpLayout {
+    pub opcode: usize,
+    pub extended_value: usize,
+}
+
+pub struct ObjectLayout {
+    pub ce: usize,
+}
+
+/// `sapi_globals_struct`
+pub struct SapiGlobalsLayout {
+    /// Up to `global_request_time`
+    pub size: usize,
+    pub request_method: usize,
+    pub query_string: usize,
+    pub request_uri: usize,
+    pub global_request_time: usize,
+}
+
+macro_rules! sapi_layout {
+    ($sapi:ident) => {
+        SapiGlobalsLayout {
+            size: size_of::<$sapi::sapi_globals_struct>(),
+            request_method: offset_of!($sapi::sapi_globals_struct, request_info.request_method),
+            query_string: offset_of!($sapi::sapi_globals_struct, request_info.query_string),
+            request_uri: offset_of!($sapi::sapi_globals_struct, request_info.request_uri),
+            global_request_time: offset_of!($sapi::sapi_globals_struct, global_request_time),
+        }
+    };
+}
+
+impl SapiGlobalsLayout {
+    fn new(abi: Abi) -> SapiGlobalsLayout
+    {
+        match abi {
+            Abi::Lp64 => sapi_layout!(sapi),
+            Abi::Ilp32 => sapi_layout!(ilp32),
+            Abi::Ilp32Arm => {
+                // `read_post_bytes` happens to be 8-aligned already, but
+                // `struct stat64` is 8-aligned and 8 bytes longer, and
+                // `global_request_time` 8-aligned
+                let mut layout = sapi_layout!(ilp32);
+                layout.global_request_time += 12;
+                layout.size += 12;
+                layout
+            }
+        }
+    }
+
+    /// 5.4 and 5.5 have `post_data`, `raw_post_data` and their lengths where
+    /// 5.6 has `request_body`, and an `int` `read_post_bytes` without
+    /// `post_read`
+    fn php54(abi: Abi) -> SapiGlobalsLayout
+    {
+        let mut layout = SapiGlobalsLayout::new(abi);
+        layout.request_uri += 2 * abi.word() + 8;
+        layout.global_request_time += 8;
+        layout.size += 8;
+        layout
+    }
+}
+
+pub struct GeneratorLayout {
+    pub execute_data: usize,
+}
+
+pub struct HashTableLayout {
+    pub size: usize,
+    pub table_mask: usize,
+    pub table_size: usize,
+    pub num_of_elements: usize,
+    /// `arData`, or `arBuckets` in PHP 5
+    pub data: usize,
+}
+
+pub struct ZvalLayout {
+    pub size: usize,
+    pub type_: usize,
+}
+
+pub struct StringLayout {
+    pub len: usize,
+    pub val: usize,
+}
+
+/// Fields only PHP 7 has
+pub struct Php7Layout {
+    /// `EX(This)`, holding the number of arguments in its `u2`
+    pub this: usize,
+    /// `EX(called_scope)`, only in PHP 7.0; later versions keep the called
+    /// class in `EX(This)` of static calls
+    pub called_scope: Option<usize>,
+    pub zval_u2: usize,
+    pub string: StringLayout,
+    pub hash_num_used: usize,
+    pub resource_handle: usize,
+    pub reference_val: usize,
+    /// `node` of a generator: `parent`, the generator it delegates to with
+    /// `yield from`, followed by the number of `children` delegating to it
+    pub generator_node: usize,
+}
+
+/// Fields only PHP 5 has
+pub struct Php5Layout {
+    /// `EX(object)`, the zval of `$this`
+    pub object: usize,
+    /// `EX(call)`, the call slot of the function being called, since 5.5
+    pub call: Option<usize>,
+    pub call_called_scope: usize,
+    /// `EX(called_scope)`, which 5.4 keeps in the execute_data itself
+    pub called_scope: Option<usize>,
+    /// `function_state.arguments`
+    pub arguments: usize,
+    /// `value.str.len` of a zval, `value.str.val` and `value.obj.handle`
+    /// being at the start of it
+    pub zval_str_len: usize,
+    pub bucket_size: usize,
+    pub bucket_valid: usize,
+    pub bucket_object: usize,
+}
+
+/// Engine generation, telling how frames, strings, values and objects are
+/// represented
+pub enum Engine {
+    Php5(Php5Layout),
+    Php7(Php7Layout),
+}
+
+/// Where the fields the reader uses are in one PHP version, taken from the
+/// bundled bindings unless the executable has debug info
+pub struct Layout {
+    /// Opcode names by number
+    pub opcodes: &'static [&'static str],
+    /// Size of a pointer, `long` and `size_t` in the process
+    pub word: usize,
+    pub engine: Engine,
+    pub executor_globals: ExecutorGlobalsLayout,
+    pub execute_data: ExecuteDataLayout,
+    pub function: FunctionLayout,
+    pub class_entry: ClassEntryLayout,
+    pub op: OpLayout,
+    pub object: ObjectLayout,
+    pub generator: GeneratorLayout,
+    pub hash_table: HashTableLayout,
+    pub zval: ZvalLayout,
+    pub sapi_globals: SapiGlobalsLayout,
+}
+
+/// Layout shared by the PHP 5 and 7 bindings, which mostly use the same
+/// field names
+macro_rules! common_layout {
+    ($php:ident, $opcodes:expr, $abi:expr, $engine:expr, $func:ident $(. $func_field:ident)*, $data:ident) => {
+        Layout {
+            opcodes: $opcodes,
+            word: $abi.word(),
+            engine: $engine,
+            executor_globals: ExecutorGlobalsLayout {
+                size: size_of::<$php::zend_executor_globals>(),
+                function_table: offset_of!($php::zend_executor_globals, function_table),
+                class_table: offset_of!($php::zend_executor_globals, class_table),
+                zend_constants: offset_of!($php::zend_executor_globals, zend_constants),
+                current_execute_data: offset_of!($php::zend_executor_globals, current_execute_data),
+                precision: offset_of!($php::zend_executor_globals, precision),
+                object_buckets: offset_of!($php::zend_executor_globals, objects_store.object_buckets),
+                objects_top: offset_of!($php::zend_executor_globals, objects_store.top),
+            },
+            execute_data: ExecuteDataLayout {
+                size: size_of::<$php::zend_execute_data>(),
+                opline: offset_of!($php::zend_execute_data, opline),
+                func: offset_of!($php::zend_execute_data, $func $(. $func_field)*),
+                prev_execute_data: offset_of!($php::zend_execute_data, prev_execute_data),
+            },
+            function: FunctionLayout {
+                size: size_of::<$php::zend_function>(),
+                type_: offset_of!($php::zend_function, type_),
+                function_name: offset_of!($php::zend_function, common.function_name),
+                scope: offset_of!($php::zend_function, common.scope),
+                filename: offset_of!($php::zend_function, op_array.filename),
+                line_start: offset_of!($php::zend_function, op_array.line_start),
+                num_args: offset_of!($php::zend_function, op_array.num_args),
+                last_var: offset_of!($php::zend_function, op_array.last_var),
+                t: offset_of!($php::zend_function, op_array.T),
+            },
+            class_entry: ClassEntryLayout {
+                name: offset_of!($php::zend_class_entry, name),
+            },
+            op: OpLayout {
+                opcode: offset_of!($php::zend_op, opcode),
+                extended_value: offset_of!($php::zend_op, extended_value),
+            },
+            object: ObjectLayout {
+                ce: offset_of!($php::zend_object, ce),
+            },
+            generator: GeneratorLayout {
+                execute_data: offset_of!($php::zend_generator, execute_data),
+            },
+            hash_table: HashTableLayout {
+                size: size_of::<$php::HashTable>(),
+                table_mask: offset_of!($php::HashTable, nTableMask),
+                table_size: offset_of!($php::HashTable, nTableSize),
+                num_of_elements: offset_of!($php::HashTable, nNumOfElements),
+                data: offset_of!($php::HashTable, $data),
+            },
+            zval: ZvalLayout {
+                size: size_of::<$php::zval>(),
+                type_: zval_type_offset!($php),
+            },
+            sapi_globals: SapiGlobalsLayout::new($abi),
+        }
+    };
+}
+
+macro_rules! zval_type_offset {
+    (php56) => { offset_of!(php56::zval, type_) };
+    (php56_32) => { offset_of!(php56_32::zval, type_) };
+    ($php:ident) => { offset_of!($php::zval, u1.v.type_) };
+}
+
+macro_rules! php5_layout {
+    ($php:ident, $abi:expr) => {
+        common_layout!($php, opcodes::PHP56, $abi, Engine::Php5(Php5Layout {
+            object: offset_of!($php::zend_execute_data, object),
+            call: Some(offset_of!($php::zend_execute_data, call)),
+            call_called_scope: offset_of!($php::call_slot, called_scope),
+            called_scope: None,
+            arguments: offset_of!($php::zend_execute_data, function_state.arguments),
+            zval_str_len: offset_of!($php::zval, value.str.len),
+            bucket_size: size_of::<$php::zend_object_store_bucket>(),
+            bucket_valid: offset_of!($php::zend_object_store_bucket, valid),
+            bucket_object: offset_of!($php::zend_object_store_bucket, bucket.obj.object),
+        }), function_state.function, arBuckets)
+    };
+}
+
+macro_rules! php7_layout {
+    ($php:ident, $opcodes:expr, $abi:expr) => {
+        common_layout!($php, $opcodes, $abi, Engine::Php7(Php7Layout {
+            this: offset_of!($php::zend_execute_data, This),
+            called_scope: None,
+            zval_u2: offset_of!($php::zval, u2),
+            string: StringLayout {
+                len: offset_of!($php::zend_string, len),
+                val: offset_of!($php::zend_string, val),
+            },
+            hash_num_used: offset_of!($php::HashTable, nNumUsed),
+            resource_handle: offset_of!($php::zend_resource, handle),
+            reference_val: offset_of!($php::zend_reference, val),
+            generator_node: generator_node(
+                offset_of!($php::zend_generator, frozen_call_stack) + $abi.word(),
+                size_of::<$php::zval>(),
+                if $abi == Abi::Ilp32Arm { 8 } else { align_of::<$php::zval>() },
+                $abi.word(),
+            ),
+        }), func, arData)
+    };
+}
+
+/// The bindings stop at `frozen_call_stack`, which `value`, `key`,
+/// `retval`, `send_target`, `largest_used_integer_key` and `values` follow
+/// before `node`
+const fn generator_node(after_call_stack: usize, zval_size: usize, zval_align: usize, word: usize) -> usize
+{
+    after_call_stack.next_multiple_of(zval_align) + 4 * zval_size + 2 * word
+}
+
+impl Layout {
+    pub fn php56(abi: Abi) -> Layout
+    {
+        match abi {
+            Abi::Lp64 => php5_layout!(php56, abi),
+            Abi::Ilp32 => php5_layout!(php56_32, abi),
+            Abi::Ilp32Arm => {
+                // The zvals at the start of EG are 8-aligned
+                let mut layout = php5_layout!(php56_32, abi);
+                layout.executor_globals.shift(8);
+                layout
+            }
+        }
+    }
+
+    /// Same layout as 5.6, without `delayed_exception` before the call
+    /// slots
+    pub fn php55(abi: Abi) -> Layout
+    {
+        let mut layout = Layout::php56(abi);
+        layout.opcodes = opcodes::PHP55;
+        if let Engine::Php5(php5) = &mut layout.engine {
+            php5.call = php5.call.map(|call| call - abi.word());
+        }
+        layout.execute_data.size -= abi.word();
+        layout.sapi_globals = SapiGlobalsLayout::php54(abi);
+        layout
+    }
+
+    /// 5.4 has no call slots: the function being called and its scope are
+    /// in the execute_data, which also holds the temporary and compiled
+    /// variables. `EG` has the `arg_types_stack` the call slots replaced and
+    /// `op_array` lacks the 5.5 fields for nested calls and `finally`.
+    pub fn php54(abi: Abi) -> Layout
+    {
+        let word = abi.word();
+        let mut layout = Layout::php56(abi);
+        layout.opcodes = opcodes::PHP54;
+        if let Engine::Php5(php5) = &mut layout.engine {
+            php5.object = 6 * word;
+            php5.call = None;
+            php5.called_scope = Some(4 * word);
+        }
+        layout.execute_data.prev_execute_data = 10 * word;
+        layout.execute_data.size = 18 * word;
+
+        // nested_calls, used_stack and has_finally_block, partly padding
+        // on LP64
+        let removed = if abi == Abi::Lp64 { 8 } else { 12 };
+        let func = &mut layout.function;
+        func.filename -= removed;
+        func.line_start -= removed;
+        func.size -= removed;
+
+        // sizeof(zend_ptr_stack)
+        let arg_types_stack = if abi == Abi::Lp64 { 32 } else { 20 };
+        layout.executor_globals.shift(arg_types_stack);
+        layout.sapi_globals = SapiGlobalsLayout::php54(abi);
+        layout
+    }
+
+    /// Same layout as 7.1, except `execute_data` still has `called_scope`,
+    /// `op_array` has `this_var` and `EG` lacks the 7.1 timeout fields
+    /// before `objects_store`
+    pub fn php70(abi: Abi) -> Layout
+    {
+        let word = abi.word();
+        let mut layout = Layout::php71(abi);
+        layout.opcodes = opcodes::PHP70;
+        if let Engine::Php7(php7) = &mut layout.engine {
+            php7.called_scope = Some(php7.this + layout.zval.size);
+        }
+        layout.execute_data.prev_execute_data += word;
+        layout.execute_data.size += word;
+
+        // this_var fits in padding on LP64
+        if abi != Abi::Lp64 {
+            let func = &mut layout.function;
+            for field in [&mut func.last_var, &mut func.t, &mut func.filename, &mut func.line_start, &mut func.size] {
+                *field += 4;
+            }
+        }
+
+        // vm_interrupt, timed_out and hard_timeout, less the padding that
+        // realigns the zvals following them on armv7
+        let removed = match abi {
+            Abi::Lp64 => 8,
+            Abi::Ilp32 => 4,
+            Abi::Ilp32Arm => 0,
+        };
+        let eg = &mut layout.executor_globals;
+        eg.object_buckets -= removed;
+        eg.objects_top -= removed;
+        // EG ends with `trampoline`, a zend_function, which this_var grows
+        // back by as much on ILP32
+        if abi == Abi::Lp64 {
+            eg.size -= removed;
+        }
+        layout
+    }
+
+    /// The 7.1 structs the reader uses are laid out as in 7.2
+    pub fn php71(abi: Abi) -> Layout
+    {
+        let mut layout = Layout::php72(abi);
+        layout.opcodes = opcodes::PHP71;
+        layout
+    }
+
+    pub fn php72(abi: Abi) -> Layout
+    {
+        match abi {
+            Abi::Lp64 => php7_layout!(php72, opcodes::PHP72, abi),
+            Abi::Ilp32 | Abi::Ilp32Arm => php7_layout!(php72_32, opcodes::PHP72, abi),
+        }
+    }
+
+    pub fn php73(abi: Abi) -> Layout
+    {
+        match abi {
+            Abi::Lp64 => php7_layout!(php73, opcodes::PHP73, abi),
+            Abi::Ilp32 | Abi::Ilp32Arm => php7_layout!(php73_32, opcodes::PHP73, abi),
+        }
+    }
+
+    /// Layout for a version, given as `major.minor`, in a process of the ABI
+    pub fn for_version(version: &str, abi: Abi) -> Option<Layout>
+    {
+        let layout = match version {
+            "5.4" => Layout::php54(abi),
+            "5.5" => Layout::php55(abi),
+            "5.6" => Layout::php56(abi),
+            "7.0" => Layout::php70(abi),
+            "7.1" => Layout::php71(abi),
+            "7.2" => Layout::php72(abi),
+            "7.3" => Layout::php73(abi),
+            _ => return None,
+        };
+        Some(if abi == Abi::Ilp32Arm { layout.padded_for_arm() } else { layout })
+    }
+
+    /// Layout of the last supported release of the major version, the base
+    /// of newer releases whose structs all come from DWARF
+    pub fn for_major(version: &str, abi: Abi) -> Option<Layout>
+    {
+        match version.split('.').next()? {
+            "5" => Layout::for_version("5.6", abi),
+            "7" => Layout::for_version("7.3", abi),
+            _ => None,
+        }
+    }
+
+    /// The i686 bindings serve armv7, where the structs holding zvals are
+    /// 8-aligned and so padded at the end: EG, and the execute_data of PHP 7
+    fn padded_for_arm(mut self) -> Layout
+    {
+        self.executor_globals.size = self.executor_globals.size.next_multiple_of(8);
+        if let Engine::Php7(_) = self.engine {
+            self.execute_data.size = self.execute_data.size.next_multiple_of(8);
+        }
+        self
+    }
+
+    /// Replace the offsets found in the debug info of the executable, so
+    /// patched or differently configured builds are read right
+    pub fn with_dwarf(mut self, structs: &HashMap<String, StructLayout>) -> Layout
+    {
+        let set = |field: &mut usize, name: &str, member: &str| {
+            if let Some(offset) = structs.get(name).and_then(|layout| layout.offset(member)) {
+                *field = offset;
+            }
+        };
+
+        let eg = &mut self.executor_globals;
+        set(&mut eg.function_table, "_zend_executor_globals", "function_table");
+        set(&mut eg.class_table, "_zend_executor_globals", "class_table");
+        set(&mut eg.zend_constants, "_zend_executor_globals", "zend_constants");
+        set(&mut eg.current_execute_data, "_zend_executor_globals", "current_execute_data");
+        set(&mut eg.precision, "_zend_executor_globals", "precision");
+        set(&mut eg.object_buckets, "_zend_executor_globals", "objects_store.object_buckets");
+        set(&mut eg.objects_top, "_zend_executor_globals", "objects_store.top");
+
+        let ex = &mut self.execute_data;
+        set(&mut ex.opline, "_zend_execute_data", "opline");
+        set(&mut ex.prev_execute_data, "_zend_execute_data", "prev_execute_data");
+        match &mut self.engine {
+            Engine::Php7(php7) => {
+                set(&mut ex.func, "_zend_execute_data", "func");
+                set(&mut php7.this, "_zend_execute_data", "This");
+                if let Some(called_scope) = &mut php7.called_scope {
+                    set(called_scope, "_zend_execute_data", "called_scope");
+                }
+                set(&mut php7.string.len, "_zend_string", "len");
+                set(&mut php7.string.val, "_zend_string", "val");
+                set(&mut php7.generator_node, "_zend_generator", "node");
+            }
+            Engine::Php5(php5) => {
+                set(&mut ex.func, "_zend_execute_data", "function_state.function");
+                set(&mut php5.arguments, "_zend_execute_data", "function_state.arguments");
+                set(&mut php5.object, "_zend_execute_data", "object");
+                if let Some(call) = &mut php5.call {
+                    set(call, "_zend_execute_data", "call");
+                }
+                if let Some(called_scope) = &mut php5.called_scope {
+                    set(called_scope, "_zend_execute_data", "called_scope");
+                }
+            }
+        }
+
+        let func = &mut self.function;
+        set(&mut func.type_, "_zend_function", "type");
+        set(&mut func.function_name, "_zend_function", "common.function_name");
+        set(&mut func.scope, "_zend_function", "common.scope");
+        set(&mut func.filename, "_zend_function", "op_array.filename");
+        set(&mut func.line_start, "_zend_function", "op_array.line_start");
+        set(&mut func.num_args, "_zend_function", "op_array.num_args");
+        set(&mut func.last_var, "_zend_function", "op_array.last_var");
+        set(&mut func.t, "_zend_function", "op_array.T");
+
+        set(&mut self.class_entry.name, "_zend_class_entry", "name");
+        set(&mut self.generator.execute_data, "_zend_generator", "execute_data");
+
+        let sg = &mut self.sapi_globals;
+        set(&mut sg.request_method, "_sapi_globals_struct", "request_info.request_method");
+        set(&mut sg.query_string, "_sapi_globals_struct", "request_info.query_string");
+        set(&mut sg.request_uri, "_sapi_globals_struct", "request_info.request_uri");
+        set(&mut sg.global_request_time, "_sapi_globals_struct", "global_request_time");
+
+        if let Some(layout) = structs.get("_zend_executor_globals") {
+            self.executor_globals.size = layout.size;
+        }
+        if let Some(layout) = structs.get("_zend_execute_data") {
+            self.execute_data.size = layout.size;
+        }
+        if let Some(layout) = structs.get("_zend_function") {
+            self.function.size = layout.size;
+        }
+        if let Some(layout) = structs.get("_sapi_globals_struct") {
+            self.sapi_globals.size = layout.size;
+        }
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `EG`: size, function_table, class_table, zend_constants,
+    /// current_execute_data, precision, objects_store.object_buckets and
+    /// objects_store.top
+    type Globals = [usize; 8];
+    /// `execute_data`: size, opline, func and prev_execute_data
+    type ExecuteData = [usize; 4];
+    /// `zend_function`: size, function_name, scope, filename, line_start,
+    /// num_args, last_var and T
+    type Function = [usize; 8];
+    /// `SG`: size, request_info.request_method, request_info.query_string,
+    /// request_info.request_uri and global_request_time
+    type Sapi = [usize; 5];
+
+    /// Offsets in the PHP headers compiled for each ABI
+    const EXPECTED: &[(&str, Abi, Globals, ExecuteData, Function, Sapi)] = &[
+        ("5.4", Abi::Lp64, [1296, 568, 576, 584, 1152, 616, 960, 968], [144, 0, 8, 80], [240, 8, 16, 144, 152, 40, 88, 92], [456, 8, 16, 72, 448]),
+        ("5.4", Abi::Ilp32, [716, 308, 312, 316, 644, 332, 532, 536], [72, 0, 4, 40], [136, 4, 8, 80, 84, 20, 48, 52], [276, 4, 8, 40, 268]),
+        ("5.4", Abi::Ilp32Arm, [728, 316, 320, 324, 652, 340, 540, 544], [72, 0, 4, 40], [136, 4, 8, 80, 84, 20, 48, 52], [288, 4, 8, 40, 280]),
+        ("5.5", Abi::Lp64, [1264, 536, 544, 552, 1120, 584, 928, 936], [128, 0, 8, 48], [248, 8, 16, 152, 160, 40, 88, 92], [456, 8, 16, 72, 448]),
+        ("5.5", Abi::Ilp32, [696, 288, 292, 296, 624, 312, 512, 516], [64, 0, 4, 24], [148, 4, 8, 92, 96, 20, 48, 52], [276, 4, 8, 40, 268]),
+        ("5.5", Abi::Ilp32Arm, [704, 296, 300, 304, 632, 320, 520, 524], [64, 0, 4, 24], [148, 4, 8, 92, 96, 20, 48, 52], [288, 4, 8, 40, 280]),
+        ("5.6", Abi::Lp64, [1264, 536, 544, 552, 1120, 584, 928, 936], [136, 0, 8, 48], [248, 8, 16, 152, 160, 40, 88, 92], [448, 8, 16, 48, 440]),
+        ("5.6", Abi::Ilp32, [696, 288, 292, 296, 624, 312, 512, 516], [68, 0, 4, 24], [148, 4, 8, 92, 96, 20, 48, 52], [268, 4, 8, 24, 260]),
+        ("5.6", Abi::Ilp32Arm, [704, 296, 300, 304, 632, 320, 520, 524], [68, 0, 4, 24], [148, 4, 8, 92, 96, 20, 48, 52], [280, 4, 8, 24, 272]),
+        ("7.0", Abi::Lp64, [1584, 432, 440, 448, 480, 496, 816, 824], [88, 0, 24, 56], [224, 8, 16, 120, 128, 32, 72, 76], [448, 8, 16, 48, 440]),
+        ("7.0", Abi::Ilp32, [988, 268, 272, 276, 292, 300, 520, 524], [48, 0, 12, 36], [140, 8, 12, 80, 84, 20, 48, 52], [268, 4, 8, 24, 260]),
+        ("7.0", Abi::Ilp32Arm, [992, 268, 272, 276, 292, 300, 524, 528], [48, 0, 12, 36], [140, 8, 12, 80, 84, 20, 48, 52], [280, 4, 8, 24, 272]),
+        ("7.1", Abi::Lp64, [1592, 432, 440, 448, 480, 496, 824, 832], [80, 0, 24, 48], [224, 8, 16, 120, 128, 32, 72, 76], [448, 8, 16, 48, 440]),
+        ("7.1", Abi::Ilp32, [988, 268, 272, 276, 292, 300, 524, 528], [44, 0, 12, 32], [136, 8, 12, 76, 80, 20, 44, 48], [268, 4, 8, 24, 260]),
+        ("7.1", Abi::Ilp32Arm, [992, 268, 272, 276, 292, 300, 524, 528], [48, 0, 12, 32], [136, 8, 12, 76, 80, 20, 44, 48], [280, 4, 8, 24, 272]),
+        ("7.2", Abi::Lp64, [1592, 432, 440, 448, 480, 496, 824, 832], [80, 0, 24, 48], [224, 8, 16, 120, 128, 32, 72, 76], [448, 8, 16, 48, 440]),
+        ("7.2", Abi::Ilp32, [988, 268, 272, 276, 292, 300, 524, 528], [44, 0, 12, 32], [136, 8, 12, 76, 80, 20, 44, 48], [268, 4, 8, 24, 260]),
+        ("7.2", Abi::Ilp32Arm, [992, 268, 272, 276, 292, 300, 524, 528], [48, 0, 12, 32], [136, 8, 12, 76, 80, 20, 44, 48], [280, 4, 8, 24, 272]),
+        ("7.3", Abi::Lp64, [1600, 432, 440, 448, 488, 504, 840, 848], [72, 0, 24, 48], [216, 8, 16, 128, 136, 32, 52, 56], [448, 8, 16, 48, 440]),
+        ("7.3", Abi::Ilp32, [1000, 268, 272, 276, 296, 304, 540, 544], [44, 0, 12, 32], [132, 8, 12, 84, 88, 20, 36, 40], [268, 4, 8, 24, 260]),
+        ("7.3", Abi::Ilp32Arm, [1000, 268, 272, 276, 296, 304, 540, 544], [48, 0, 12, 32], [132, 8, 12, 84, 88, 20, 36, 40], [280, 4, 8, 24, 272]),
+    ];
+
+    #[test]
+    fn every_layout_matches_the_headers()
+    {
+        for (version, abi, globals, execute_data, function, sapi) in EXPECTED {
+            let layout = Layout::for_version(version, *abi).unwrap();
+            let eg = &layout.executor_globals;
+            let ex = &layout.execute_data;
+            let func = &layout.function;
+            let context = format!("PHP {} {:?}", version, abi);
+
+            assert_eq!(layout.word, abi.word(), "{}", context);
+            assert_eq!(
+                [
+                    eg.size,
+                    eg.function_table,
+                    eg.class_table,
+                    eg.zend_constants,
+                    eg.current_execute_data,
+                    eg.precision,
+                    eg.object_buckets,
+                    eg.objects_top,
+                ],
+                *globals,
+                "EG of {}",
+                context
+            );
+            assert_eq!([ex.size, ex.opline, ex.func, ex.prev_execute_data], *execute_data, "execute_data of {}", context);
+            assert_eq!(
+                [
+                    func.size,
+                    func.function_name,
+                    func.scope,
+                    func.filename,
+                    func.line_start,
+                    func.num_args,
+                    func.last_var,
+                    func.t,
+                ],
+                *function,
+                "zend_function of {}",
+                context
+            );
+            // The LP64 `struct stat` is the host's, 144 bytes on x86_64
+            if *abi != Abi::Lp64 || cfg!(target_arch = "x86_64") {
+                let sg = &layout.sapi_globals;
+                assert_eq!(
+                    [sg.size, sg.request_method, sg.query_string, sg.request_uri, sg.global_request_time],
+                    *sapi,
+                    "SG of {}",
+                    context
+                );
+            }
+        }
+    }
+
+    /// Pointers are word aligned, and structs holding them a whole number
+    /// of words
+    #[test]
+    fn pointers_are_word_aligned()
+    {
+        for (version, abi, ..) in EXPECTED {
+            let layout = Layout::for_version(version, *abi).unwrap();
+            let eg = &layout.executor_globals;
+            let ex = &layout.execute_data;
+            let func = &layout.function;
+            let sg = &layout.sapi_globals;
+            let context = format!("PHP {} {:?}", version, abi);
+
+            let pointers = [
+                eg.function_table,
+                eg.class_table,
+                eg.zend_constants,
+                eg.current_execute_data,
+                eg.object_buckets,
+                ex.opline,
+                ex.func,
+                ex.prev_execute_data,
+                func.function_name,
+                func.scope,
+                func.filename,
+                layout.class_entry.name,
+                layout.generator.execute_data,
+                sg.request_method,
+                sg.query_string,
+                sg.request_uri,
+            ];
+            for offset in pointers {
+                assert_eq!(offset % abi.word(), 0, "pointer at {} in {}", offset, context);
+            }
+            for size in [eg.size, ex.size, func.size, sg.size] {
+                assert_eq!(size % abi.word(), 0, "size {} in {}", size, context);
+            }
+        }
+    }
+
+    /// armv7 aligns 8-byte members to 8 where i686 aligns them to 4, which
+    /// only pads the structs holding them
+    #[test]
+    fn arm_pads_the_i686_layout()
+    {
+        for (version, ..) in EXPECTED.iter().filter(|(_, abi, ..)| *abi == Abi::Ilp32) {
+            let i686 = Layout::for_version(version, Abi::Ilp32).unwrap();
+            let arm = Layout::for_version(version, Abi::Ilp32Arm).unwrap();
+            let context = format!("PHP {}", version);
+
+            // No 8-byte member before the fields read
+            let (ex, arm_ex) = (&i686.execute_data, &arm.execute_data);
+            assert_eq!(
+                [ex.opline, ex.func, ex.prev_execute_data],
+                [arm_ex.opline, arm_ex.func, arm_ex.prev_execute_data],
+                "{}",
+                context
+            );
+            assert_eq!(i686.function.size, arm.function.size, "{}", context);
+
+            // zvals in EG, and `global_request_time`, are 8-aligned
+            assert_eq!(arm.executor_globals.size % 8, 0, "{}", context);
+            assert_eq!(arm.sapi_globals.global_request_time % 8, 0, "{}", context);
+            let (eg, arm_eg) = (&i686.executor_globals, &arm.executor_globals);
+            let fields = [
+                (eg.function_table, arm_eg.function_table),
+                (eg.current_execute_data, arm_eg.current_execute_data),
+                (eg.objects_top, arm_eg.objects_top),
+                (eg.size, arm_eg.size),
+                (i686.sapi_globals.global_request_time, arm.sapi_globals.global_request_time),
+            ];
+            // Padding only moves fields forward, by at most 12 bytes in
+            // these structs
+            for (i686, arm) in fields {
+                assert!(arm >= i686 && arm - i686 <= 12, "{} on i686, {} on ARM in {}", i686, arm, context);
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Read the stack of a running PHP process from outside of it.
+//!
+//! ```no_run
+//! let tracer = php_stacktrace::Tracer::attach(1234)?;
+//! for frame in tracer.snapshot()?.frames {
+//!     println!("{}", frame);
+//! }
+//! # Ok::<(), php_stacktrace::Error>(())
+//! ```
+
+mod attach;
+mod debuginfo;
+mod dwarf;
+mod error;
+mod layout;
+mod native;
+mod opcodes;
+mod php56;
+mod php56_32;
+mod php72;
+mod php72_32;
+mod php73;
+mod php73_32;
+mod process_reader;
+mod procfs;
+mod symbol_cache;
+mod syscall;
+mod tracer;
+
+pub mod export;
+pub mod recording;
+pub mod sapi;
+
+pub use crate::debuginfo::{get_build_id, get_php_version, get_sapi_globals_address, get_symbol_address};
+pub use crate::error::{Error, Result};
+pub use crate::native::NativeFrame;
+pub use crate::procfs::{is_alive, namespace_pid};
+pub use crate::process_reader::{Coroutine, Frame, FrameKind, Trace};
+pub use crate::syscall::Blocked;
+pub use crate::tracer::Tracer;
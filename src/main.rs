@@ -1,70 +1,400 @@
-mod debuginfo;
-mod attach;
-mod process_reader;
-mod php73;
-mod php72;
-mod php56;
-
-use read_process_memory::*;
-use std::time;
-use clap::{App, Arg, ArgMatches};
-use crate::debuginfo::*;
-use crate::process_reader::ProcessReader;
+mod top;
+mod record;
+mod report;
+mod diff;
+mod watchdog;
+mod watch;
+mod server;
+mod metrics;
 
-fn create_reader(version: &str, source: ProcessHandle) -> Box<dyn ProcessReader>
-{
-    if version == "5.6" {
-        Box::new(process_reader::PHP560{source})
-    } else if version == "7.2" {
-        Box::new(process_reader::PHP720{source})
-    } else {
-        Box::new(process_reader::PHP730{source})
-    }
-}
+use std::fmt::Display;
+use std::process;
+use std::str::FromStr;
+use std::time;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use php_stacktrace::{Result, Tracer};
+use read_process_memory::Pid;
 
 fn main()
 {
     let matches = parse_args();
 
-    let pid: Pid = matches.value_of("PID").unwrap().parse().unwrap();
-
-    let source = pid.try_into_process_handle().unwrap();
+    match matches.subcommand() {
+        ("top", Some(matches)) => return top::run(matches),
+        ("record", Some(matches)) => return record::run(matches),
+        ("report", Some(matches)) => return report::run(matches),
+        ("diff", Some(matches)) => return diff::run(matches),
+        ("watchdog", Some(matches)) => return watchdog::run(matches),
+        ("watch", Some(matches)) => return watch::run(matches),
+        ("serve", Some(matches)) => return server::run(matches),
+        _ => {}
+    }
 
-    let addr = get_executor_globals_address(source);
+    let pid: Pid = arg_value(&matches, "PID");
 
-    let php_version = matches.value_of("PHP Version").unwrap();
-    let php = create_reader(php_version, source);
+    let mut tracer = create_tracer(pid, matches.value_of("PHP Version")).unwrap_or_else(|e| fail(e));
+    tracer.coroutines = matches.is_present("coroutines");
+    tracer.native = matches.is_present("native");
+    tracer.args = matches.is_present("args");
 
     let start_time  = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap();
 
-    attach::attach(pid);
-
-    php.read(addr);
+    let trace = tracer.snapshot().unwrap_or_else(|e| fail(e));
 
-    attach::detach(pid);
+    print!("{}", trace);
 
     let end_time = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap();
     let dur = end_time - start_time;
     println!("Time {:?}", dur);
 }
 
+/// Tracer for the version given with `-v`, or the detected one
+fn create_tracer(pid: Pid, version: Option<&str>) -> Result<Tracer>
+{
+    match version {
+        Some(version) => Tracer::with_version(pid, version),
+        None => Tracer::attach(pid),
+    }
+}
+
+fn fail(e: impl Display) -> !
+{
+    eprintln!("{}", e);
+    process::exit(1)
+}
+
+/// Value of an argument, already checked by its validator
+fn arg_value<T>(matches: &ArgMatches, name: &str) -> T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_value(name, matches.value_of(name).unwrap())
+}
+
+fn parse_value<T>(name: &str, value: &str) -> T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().unwrap_or_else(|e| fail(format!("invalid {} {}: {}", name, value, e)))
+}
+
+/// Rates, counts and durations in whole units, 0 would divide by zero or
+/// do nothing
+fn is_positive(value: String) -> std::result::Result<(), String> {
+    match value.parse::<u32>() {
+        Ok(number) if number > 0 => Ok(()),
+        _ => Err(format!("{} is not a positive integer", value)),
+    }
+}
+
+/// Durations in seconds, possibly fractional
+fn is_seconds(value: String) -> std::result::Result<(), String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => Ok(()),
+        _ => Err(format!("{} is not a number of seconds", value)),
+    }
+}
+
+fn is_pid(value: String) -> std::result::Result<(), String> {
+    match value.parse::<Pid>() {
+        Ok(pid) if pid > 0 => Ok(()),
+        _ => Err(format!("{} is not a PID", value)),
+    }
+}
+
+fn version_arg() -> Arg<'static, 'static> {
+    Arg::with_name("PHP Version")
+        .value_name("php_version")
+        .short("v")
+        .help("PHP Version (5.4, 5.5, 5.6, 7.0, 7.1, 7.2, 7.3), detected from the executable by default")
+        .required(false)
+}
+
+fn rate_arg() -> Arg<'static, 'static> {
+    Arg::with_name("rate")
+        .short("r")
+        .long("rate")
+        .value_name("hz")
+        .help("Samples per second, per process")
+        .default_value("100")
+        .validator(is_positive)
+}
+
+fn pids_arg() -> Arg<'static, 'static> {
+    Arg::with_name("PID")
+        .help("PIDs of the PHP processes")
+        .required(true)
+        .multiple(true)
+        .validator(is_pid)
+}
+
 fn parse_args() -> ArgMatches<'static> {
     App::new("php-stacktrace")
         .version("0.2.0")
         .about("Read stacktrace from outside PHP process")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .setting(AppSettings::VersionlessSubcommands)
+        .arg(version_arg())
         .arg(
-            Arg::with_name("PHP Version")
-                .value_name("php_version")
-                .short("v")
-                .help("PHP Version (5.6, 7.2, 7.3)")
-                .default_value("7.3")
-                .required(false),
+            Arg::with_name("coroutines")
+                .short("c")
+                .long("coroutines")
+                .help("Also print the frames of suspended generators, through `yield from` (not fibers)"),
+        )
+        .arg(
+            Arg::with_name("native")
+                .short("n")
+                .long("native")
+                .help("Interleave native frames with PHP frames"),
+        )
+        .arg(
+            Arg::with_name("args")
+                .short("a")
+                .long("args")
+                .help("Print the arguments of each call"),
         )
         .arg(
             Arg::with_name("PID")
                 .help("PID of the PHP process")
                 .required(true)
-                .index(1),
+                .index(1)
+                .validator(is_pid),
+        )
+        .subcommand(
+            SubCommand::with_name("top")
+                .about("Continuously show the most frequent functions")
+                .arg(version_arg())
+                .arg(rate_arg())
+                .arg(
+                    Arg::with_name("window")
+                        .short("w")
+                        .long("window")
+                        .value_name("seconds")
+                        .help("Length of the sliding window")
+                        .default_value("10")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("lines")
+                        .short("l")
+                        .long("lines")
+                        .value_name("count")
+                        .help("Number of functions to show")
+                        .default_value("20")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("sort")
+                        .short("s")
+                        .long("sort")
+                        .value_name("column")
+                        .help("Column to sort by")
+                        .possible_values(&["leaf", "incl"])
+                        .default_value("leaf"),
+                )
+                .arg(pids_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("record")
+                .about("Record samples to a file")
+                .arg(version_arg())
+                .arg(rate_arg())
+                .arg(
+                    Arg::with_name("duration")
+                        .short("d")
+                        .long("duration")
+                        .value_name("seconds")
+                        .help("Stop after this many seconds, instead of when the processes exit")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("file")
+                        .help("Recording file to write")
+                        .required(true),
+                )
+                .arg(pids_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("report")
+                .about("Convert a recording to text, folded stacks, JSON or pprof")
+                .arg(
+                    Arg::with_name("format")
+                        .short("f")
+                        .long("format")
+                        .value_name("format")
+                        .possible_values(&["text", "folded", "json", "pprof"])
+                        .default_value("text"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("file")
+                        .help("Write to a file instead of stdout"),
+                )
+                .arg(
+                    Arg::with_name("FILE")
+                        .help("Recording made by the record subcommand")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Compare two recordings or folded files")
+                .arg(
+                    Arg::with_name("format")
+                        .short("f")
+                        .long("format")
+                        .value_name("format")
+                        .help("Ranked table of functions, or folded input for a differential flame graph")
+                        .possible_values(&["table", "folded"])
+                        .default_value("table"),
+                )
+                .arg(
+                    Arg::with_name("lines")
+                        .short("l")
+                        .long("lines")
+                        .value_name("count")
+                        .help("Number of functions in the table")
+                        .default_value("30")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("file")
+                        .help("Write to a file instead of stdout"),
+                )
+                .arg(Arg::with_name("BEFORE").help("Baseline profile").required(true))
+                .arg(Arg::with_name("AFTER").help("Profile to compare").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("watchdog")
+                .about("Log the stack of php-fpm requests running longer than a threshold")
+                .arg(version_arg())
+                .arg(
+                    Arg::with_name("threshold")
+                        .short("t")
+                        .long("threshold")
+                        .value_name("seconds")
+                        .help("Request duration after which the stack is captured")
+                        .default_value("5")
+                        .validator(is_seconds),
+                )
+                .arg(
+                    Arg::with_name("interval")
+                        .short("i")
+                        .long("interval")
+                        .value_name("ms")
+                        .help("How often workers are checked")
+                        .default_value("100")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("file")
+                        .help("Log file, one JSON object per slow request")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("PID")
+                        .help("PIDs of the php-fpm master processes")
+                        .required(true)
+                        .multiple(true)
+                        .validator(is_pid),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("watch")
+                .about("Print traces, with arguments, whose stack matches a pattern")
+                .arg(version_arg())
+                .arg(
+                    Arg::with_name("rate")
+                        .short("r")
+                        .long("rate")
+                        .value_name("hz")
+                        .help("Samples per second, per process")
+                        .default_value("1000")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("count")
+                        .short("n")
+                        .long("count")
+                        .value_name("count")
+                        .help("Stop after this many captures")
+                        .default_value("10")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("cooldown")
+                        .short("c")
+                        .long("cooldown")
+                        .value_name("seconds")
+                        .help("Minimum time between two captures")
+                        .default_value("1")
+                        .validator(is_seconds),
+                )
+                .arg(
+                    Arg::with_name("duration")
+                        .short("d")
+                        .long("duration")
+                        .value_name("seconds")
+                        .help("Stop after this many seconds")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("file")
+                        .help("Write to a file instead of stdout"),
+                )
+                .arg(
+                    Arg::with_name("PATTERN")
+                        .help("Regex matched against the qualified name of each frame, e.g. 'Cache::flush'")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(pids_arg().index(2)),
+        )
+        .subcommand(
+            SubCommand::with_name("serve")
+                .about("Serve stacks and profiles over HTTP")
+                .arg(version_arg())
+                .arg(
+                    Arg::with_name("rate")
+                        .short("r")
+                        .long("rate")
+                        .value_name("hz")
+                        .help("Samples per second for /profile and /metrics")
+                        .default_value("100")
+                        .validator(is_positive),
+                )
+                .arg(
+                    Arg::with_name("listen")
+                        .short("l")
+                        .long("listen")
+                        .value_name("address")
+                        .help("Address to listen on")
+                        .default_value("127.0.0.1:8765"),
+                )
+                .arg(
+                    Arg::with_name("metrics")
+                        .short("m")
+                        .long("metrics")
+                        .value_name("regex")
+                        .help("Continuously sample PHP processes whose command line matches, for /metrics"),
+                ),
         )
         .get_matches()
 }
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use php_stacktrace::Trace;
+use read_process_memory::Pid;
+use regex::Regex;
+
+use crate::server::{php_processes, Tracers};
+
+/// How often the list of matched processes is refreshed
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Counters since the sampler started, and the functions on the stack of
+/// each process at its last sample
+#[derive(Default)]
+pub struct Metrics {
+    samples: u64,
+    idle_samples: u64,
+    /// Samples whose stack couldn't be read
+    failed_samples: u64,
+    /// Samples with the function on top of the stack
+    self_samples: BTreeMap<String, u64>,
+    /// Samples with the function anywhere on the stack
+    inclusive_samples: BTreeMap<String, u64>,
+    current: HashMap<Pid, BTreeSet<String>>,
+}
+
+impl Metrics {
+    fn add(&mut self, pid: Pid, trace: &Trace)
+    {
+        self.samples += 1;
+        match trace.frames.first() {
+            Some(frame) => *self.self_samples.entry(frame.name()).or_insert(0) += 1,
+            None => self.idle_samples += 1,
+        }
+
+        let functions: BTreeSet<String> = trace.frames.iter().map(|frame| frame.name()).collect();
+        for function in &functions {
+            *self.inclusive_samples.entry(function.clone()).or_insert(0) += 1;
+        }
+        self.current.insert(pid, functions);
+    }
+
+    /// Prometheus text exposition format
+    pub fn render(&self) -> String
+    {
+        let mut out = String::new();
+
+        metric_header(&mut out, "php_samples_total", "counter", "Stack samples taken");
+        writeln!(out, "php_samples_total {}", self.samples).unwrap();
+        metric_header(&mut out, "php_idle_samples_total", "counter", "Samples with no PHP code running");
+        writeln!(out, "php_idle_samples_total {}", self.idle_samples).unwrap();
+        metric_header(&mut out, "php_failed_samples_total", "counter", "Samples whose stack could not be read");
+        writeln!(out, "php_failed_samples_total {}", self.failed_samples).unwrap();
+
+        metric_header(&mut out, "php_function_self_samples_total", "counter", "Samples with the function on top of the stack");
+        for (function, count) in &self.self_samples {
+            writeln!(out, "php_function_self_samples_total{{function=\"{}\"}} {}", escape_label(function), count).unwrap();
+        }
+        metric_header(&mut out, "php_function_samples_total", "counter", "Samples with the function anywhere on the stack");
+        for (function, count) in &self.inclusive_samples {
+            writeln!(out, "php_function_samples_total{{function=\"{}\"}} {}", escape_label(function), count).unwrap();
+        }
+
+        metric_header(&mut out, "php_processes", "gauge", "Processes being sampled");
+        writeln!(out, "php_processes {}", self.current.len()).unwrap();
+
+        let mut workers: BTreeMap<&str, usize> = BTreeMap::new();
+        for function in self.current.values().flatten() {
+            *workers.entry(function).or_insert(0) += 1;
+        }
+        metric_header(&mut out, "php_workers_in_function", "gauge", "Processes inside the function at their last sample");
+        for (function, count) in workers {
+            writeln!(out, "php_workers_in_function{{function=\"{}\"}} {}", escape_label(function), count).unwrap();
+        }
+        out
+    }
+}
+
+fn metric_header(out: &mut String, name: &str, kind: &str, help: &str)
+{
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} {}", name, kind).unwrap();
+}
+
+/// Escape `\`, `"` and newlines in a label value
+fn escape_label(value: &str) -> String
+{
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Command line of a process, arguments separated by spaces
+fn cmdline(pid: Pid) -> Option<String>
+{
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    Some(String::from_utf8_lossy(&cmdline).replace('\0', " ").trim_end().to_string())
+}
+
+/// Sample the PHP processes whose command line matches the pattern in the
+/// background, forever
+pub fn spawn_sampler(tracers: Arc<Tracers>, rate: u32, pattern: Regex) -> Arc<Mutex<Metrics>>
+{
+    let metrics = Arc::new(Mutex::new(Metrics::default()));
+    let shared = Arc::clone(&metrics);
+
+    thread::spawn(move || {
+        let interval = Duration::from_secs(1) / rate;
+        let mut pids: Vec<Pid> = Vec::new();
+        // Processes that can't be traced, so they are only tried once
+        let mut failed: HashSet<Pid> = HashSet::new();
+        let mut last_scan: Option<Instant> = None;
+
+        loop {
+            let tick = Instant::now();
+
+            if last_scan.is_none_or(|last_scan| last_scan.elapsed() >= RESCAN_INTERVAL) {
+                let php_pids: Vec<Pid> = php_processes().into_iter().map(|(pid, _)| pid).collect();
+                tracers.retain(&php_pids);
+                pids = php_pids
+                    .into_iter()
+                    .filter(|pid| cmdline(*pid).is_some_and(|cmdline| pattern.is_match(&cmdline)))
+                    .collect();
+                failed.retain(|pid| pids.contains(pid));
+                shared.lock().unwrap().current.retain(|pid, _| pids.contains(pid));
+                last_scan = Some(Instant::now());
+            }
+
+            for pid in &pids {
+                if failed.contains(pid) {
+                    continue;
+                }
+                let tracer = match tracers.get(*pid) {
+                    Ok(tracer) => tracer,
+                    Err(_) => {
+                        failed.insert(*pid);
+                        continue;
+                    }
+                };
+                let snapshot = tracer.lock().unwrap().snapshot();
+                match snapshot {
+                    Ok(trace) => shared.lock().unwrap().add(*pid, &trace),
+                    Err(_) => shared.lock().unwrap().failed_samples += 1,
+                }
+            }
+
+            if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+    });
+
+    metrics
+}
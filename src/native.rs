@@ -0,0 +1,526 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use gimli::{BaseAddresses, CfaRule, EhFrame, EhFrameHdr, LittleEndian, Register, RegisterRule,
+            UnwindContext, UnwindSection};
+use object::{Object, ObjectSection, ObjectSegment, ObjectSymbol, SymbolKind};
+use read_process_memory::{copy_address, ProcessHandle};
+
+use crate::attach::Registers;
+use crate::process_reader::Frame;
+use crate::procfs::{self, MapEntry};
+
+const MAX_DEPTH: usize = 512;
+
+/// DWARF numbers of the stack pointer, frame pointer and return address
+#[cfg(not(target_arch = "aarch64"))]
+mod dwarf_regs {
+    use gimli::{Register, X86_64};
+
+    pub const SP: Register = X86_64::RSP;
+    pub const FP: Register = X86_64::RBP;
+    pub const RA: Register = X86_64::RA;
+}
+
+#[cfg(target_arch = "aarch64")]
+mod dwarf_regs {
+    use gimli::{AArch64, Register};
+
+    pub const SP: Register = AArch64::SP;
+    pub const FP: Register = AArch64::X29;
+    pub const RA: Register = AArch64::X30;
+}
+
+/// Native functions running the executor, where PHP frames get spliced in
+const EXECUTOR_SYMBOLS: &[&str] = &["execute_ex", "zend_call_function"];
+
+/// Calls a single function, from internal functions taking callbacks
+const CALL_FUNCTION_SYMBOL: &str = "zend_call_function";
+
+pub struct NativeFrame {
+    pub addr: usize,
+    pub symbol: Option<String>,
+    pub offset: usize,
+    pub module: Option<String>,
+}
+
+impl NativeFrame {
+    fn is_executor(&self) -> bool
+    {
+        match &self.symbol {
+            Some(symbol) => EXECUTOR_SYMBOLS.contains(&symbol.as_str()),
+            None => false,
+        }
+    }
+
+    fn is_call_function(&self) -> bool
+    {
+        self.symbol.as_deref() == Some(CALL_FUNCTION_SYMBOL)
+    }
+}
+
+impl fmt::Display for NativeFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "[native] ")?;
+        match &self.symbol {
+            Some(symbol) => write!(f, "{}+{:#x}", symbol, self.offset)?,
+            None => write!(f, "{:#x}", self.addr)?,
+        }
+        if let Some(module) = &self.module {
+            write!(f, " ({})", module)?;
+        }
+        Ok(())
+    }
+}
+
+pub enum StackEntry<'a> {
+    Php(&'a Frame),
+    Native(&'a NativeFrame),
+}
+
+/// Interleave PHP frames with native frames. Each `execute_ex` on the native
+/// stack runs PHP frames up to the next internal function, whose own native
+/// frames sit below it. `zend_call_function` runs one function: a user one
+/// is in the `execute_ex` above it, an internal one it calls directly.
+pub fn splice<'a>(native: &'a [NativeFrame], php: &'a [Frame]) -> Vec<StackEntry<'a>>
+{
+    let mut entries = Vec::new();
+    let mut php_frames = php.iter().peekable();
+    // An `execute_ex` was seen since the last `zend_call_function`
+    let mut executed = false;
+
+    for frame in native {
+        entries.push(StackEntry::Native(frame));
+        if !frame.is_executor() {
+            continue;
+        }
+
+        if frame.is_call_function() {
+            if !executed {
+                if let Some(php_frame) = php_frames.next_if(|php_frame| php_frame.internal) {
+                    entries.push(StackEntry::Php(php_frame));
+                }
+            }
+            executed = false;
+            continue;
+        }
+        executed = true;
+
+        while let Some(php_frame) = php_frames.next_if(|php_frame| php_frame.internal) {
+            entries.push(StackEntry::Php(php_frame));
+        }
+        while let Some(php_frame) = php_frames.next_if(|php_frame| !php_frame.internal) {
+            entries.push(StackEntry::Php(php_frame));
+        }
+    }
+
+    entries.extend(php_frames.map(StackEntry::Php));
+    entries
+}
+
+/// An executable or library, read and parsed once per tracer
+struct ModuleFile {
+    data: Vec<u8>,
+    /// File offset, file size and address of each segment
+    segments: Vec<(u64, u64, u64)>,
+    symbols: Vec<(u64, String)>,
+    text: Option<u64>,
+    eh_frame: Option<Section>,
+    eh_frame_hdr: Option<Section>,
+}
+
+/// Address of a section, and where its bytes are in the file
+struct Section {
+    address: u64,
+    range: Range<usize>,
+}
+
+impl Section {
+    fn find(file: &object::File, name: &str) -> Option<Section>
+    {
+        let section = file.section_by_name(name)?;
+        let (offset, size) = section.file_range()?;
+        Some(Section { address: section.address(), range: offset as usize..(offset + size) as usize })
+    }
+}
+
+/// Path and inode of a mapped file
+type FileKey = (String, u64);
+
+/// Parsed files of the modules, `None` for those that aren't ELF
+#[derive(Default)]
+pub struct ModuleCache {
+    files: RefCell<HashMap<FileKey, Option<Arc<ModuleFile>>>>,
+}
+
+struct Module {
+    path: String,
+    start: usize,
+    end: usize,
+    /// Runtime address minus the address in the ELF file
+    bias: usize,
+    file: Arc<ModuleFile>,
+}
+
+impl Module {
+    fn name(&self) -> String
+    {
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    fn symbolize(&self, svma: u64) -> Option<(String, usize)>
+    {
+        let symbols = &self.file.symbols;
+        let index = match symbols.binary_search_by_key(&svma, |(addr, _)| *addr) {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        let (addr, name) = &symbols[index];
+        Some((name.clone(), (svma - addr) as usize))
+    }
+
+    /// Registers of the calling frame, computed from `.eh_frame`
+    fn unwind(&self, source: &ProcessHandle, svma: u64, regs: &Registers) -> Option<Registers>
+    {
+        let file = &*self.file;
+        let eh_frame_section = file.eh_frame.as_ref()?;
+
+        let mut bases = BaseAddresses::default().set_eh_frame(eh_frame_section.address);
+        if let Some(text) = file.text {
+            bases = bases.set_text(text);
+        }
+
+        let eh_frame = EhFrame::new(file.data.get(eh_frame_section.range.clone())?, LittleEndian);
+        let mut ctx = UnwindContext::new();
+
+        let row = match &file.eh_frame_hdr {
+            Some(hdr_section) => {
+                bases = bases.set_eh_frame_hdr(hdr_section.address);
+                let hdr = EhFrameHdr::new(file.data.get(hdr_section.range.clone())?, LittleEndian)
+                    .parse(&bases, 8)
+                    .ok()?;
+                hdr.table()?
+                    .unwind_info_for_address(&eh_frame, &bases, &mut ctx, svma, EhFrame::cie_from_offset)
+                    .ok()?
+                    .clone()
+            }
+            None => eh_frame
+                .unwind_info_for_address(&bases, &mut ctx, svma, EhFrame::cie_from_offset)
+                .ok()?
+                .clone(),
+        };
+        let cfa = match row.cfa() {
+            CfaRule::RegisterAndOffset { register, offset } => {
+                (register_value(regs, *register)? as i64 + offset) as usize
+            }
+            CfaRule::Expression(_) => return None,
+        };
+
+        let restore = |register| -> Option<usize> {
+            match row.register(register) {
+                RegisterRule::Undefined | RegisterRule::SameValue => register_value(regs, register),
+                RegisterRule::Offset(offset) => read_usize(source, (cfa as i64 + offset) as usize),
+                RegisterRule::ValOffset(offset) => Some((cfa as i64 + offset) as usize),
+                RegisterRule::Register(other) => register_value(regs, other),
+                _ => None,
+            }
+        };
+
+        let ip = match row.register(dwarf_regs::RA) {
+            RegisterRule::Offset(offset) => read_usize(source, (cfa as i64 + offset) as usize)?,
+            // aarch64 leaf function, the return address is still in the link
+            // register
+            RegisterRule::Undefined | RegisterRule::SameValue if regs.lr != 0 => regs.lr,
+            _ => return None,
+        };
+
+        Some(Registers { ip, sp: cfa, bp: restore(dwarf_regs::FP)?, lr: 0, pac_mask: regs.pac_mask })
+    }
+}
+
+fn register_value(regs: &Registers, register: Register) -> Option<usize>
+{
+    match register {
+        dwarf_regs::SP => Some(regs.sp),
+        dwarf_regs::FP => Some(regs.bp),
+        dwarf_regs::RA => Some(regs.lr),
+        _ => None,
+    }
+}
+
+/// Walk the native stack of a stopped thread, starting from its registers
+pub fn unwind<Pid>(pid: Pid, source: &ProcessHandle, regs: Registers, cache: &ModuleCache) -> Vec<NativeFrame>
+where
+    Pid: std::fmt::Display,
+{
+    let modules = load_modules(pid, cache);
+    let mut frames = Vec::new();
+    let mut regs = regs;
+
+    while regs.ip != 0 && frames.len() < MAX_DEPTH {
+        // Return addresses point after the call, look up the call itself
+        let lookup = if frames.is_empty() { regs.ip } else { regs.ip - 1 };
+        let module = modules.iter().find(|m| lookup >= m.start && lookup < m.end);
+
+        let mut frame = NativeFrame { addr: regs.ip, symbol: None, offset: 0, module: None };
+        let next = match module {
+            Some(module) => {
+                let svma = (lookup - module.bias) as u64;
+                if let Some((symbol, offset)) = module.symbolize(svma) {
+                    frame.symbol = Some(symbol);
+                    frame.offset = offset + (regs.ip - lookup);
+                }
+                frame.module = Some(module.name());
+                module.unwind(source, svma, &regs)
+            }
+            None => None,
+        };
+        frames.push(frame);
+
+        regs = match next.or_else(|| unwind_frame_pointer(source, &regs)) {
+            // Signed return addresses, strip the authentication code
+            Some(next) if next.sp > regs.sp => Registers { ip: next.ip & !next.pac_mask, ..next },
+            _ => break,
+        };
+    }
+    frames
+}
+
+/// Saved frame pointer followed by the return address, as both `rbp` and
+/// `x29` frame records are
+fn unwind_frame_pointer(source: &ProcessHandle, regs: &Registers) -> Option<Registers>
+{
+    if regs.bp == 0 {
+        return None;
+    }
+    Some(Registers {
+        ip: read_usize(source, regs.bp + 8)?,
+        sp: regs.bp + 16,
+        bp: read_usize(source, regs.bp)?,
+        lr: 0,
+        pac_mask: regs.pac_mask,
+    })
+}
+
+fn read_usize(source: &ProcessHandle, addr: usize) -> Option<usize>
+{
+    let bytes = copy_address(addr, 8, source).ok()?;
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes);
+    Some(usize::from_ne_bytes(buf))
+}
+
+/// Executable file mappings of the process. Files are only read the first
+/// time they are seen, and forgotten once no longer mapped.
+fn load_modules<Pid>(pid: Pid, cache: &ModuleCache) -> Vec<Module>
+where
+    Pid: std::fmt::Display,
+{
+    let maps = match procfs::read_maps(&pid) {
+        Ok(maps) => maps,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut files = cache.files.borrow_mut();
+    let mut mapped = HashSet::new();
+    let mut modules = Vec::new();
+    for entry in maps.iter().filter(|entry| entry.is_executable()) {
+        let path = match &entry.path {
+            Some(path) => path,
+            None => continue,
+        };
+        let key = (path.clone(), entry.inode);
+        let file = files.entry(key.clone()).or_insert_with(|| load_file(&pid, entry).map(Arc::new));
+        mapped.insert(key);
+        if let Some(module) = file.as_ref().and_then(|file| map_module(entry, path, file)) {
+            modules.push(module);
+        }
+    }
+    files.retain(|key, _| mapped.contains(key));
+    modules
+}
+
+/// Place a file at the address it is mapped at
+fn map_module(entry: &MapEntry, path: &str, file: &Arc<ModuleFile>) -> Option<Module>
+{
+    let (start, end, offset) = (entry.start, entry.end, entry.offset);
+    let (file_offset, _, address) = file
+        .segments
+        .iter()
+        .find(|(file_offset, file_size, _)| offset >= *file_offset && offset < file_offset + file_size)?;
+    let bias = start - (address + offset - file_offset) as usize;
+    Some(Module { path: path.to_string(), start, end, bias, file: file.clone() })
+}
+
+fn load_file<Pid>(pid: Pid, entry: &MapEntry) -> Option<ModuleFile>
+where
+    Pid: std::fmt::Display,
+{
+    let data = procfs::read_mapped(pid, entry).ok()?;
+    let file = object::File::parse(&*data).ok()?;
+
+    let segments = file
+        .segments()
+        .map(|segment| {
+            let (file_offset, file_size) = segment.file_range();
+            (file_offset, file_size, segment.address())
+        })
+        .collect();
+
+    let mut symbols: Vec<(u64, String)> = file
+        .symbols()
+        .chain(file.dynamic_symbols())
+        .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.address() != 0)
+        .filter_map(|symbol| Some((symbol.address(), symbol.name().ok()?.to_string())))
+        .collect();
+    symbols.sort();
+    symbols.dedup_by_key(|(addr, _)| *addr);
+
+    let text = file.section_by_name(".text").map(|text| text.address());
+    let eh_frame = Section::find(&file, ".eh_frame");
+    let eh_frame_hdr = Section::find(&file, ".eh_frame_hdr");
+    drop(file);
+    Some(ModuleFile { data, segments, symbols, text, eh_frame, eh_frame_hdr })
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests {
+    use super::*;
+    use read_process_memory::{Pid, TryIntoProcessHandle};
+
+    fn native_frames(symbols: &[&str]) -> Vec<NativeFrame>
+    {
+        symbols
+            .iter()
+            .map(|symbol| NativeFrame { addr: 0, symbol: Some(symbol.to_string()), offset: 0, module: None })
+            .collect()
+    }
+
+    /// Function names, and whether each is internal
+    fn php_frames(functions: &[(&str, bool)]) -> Vec<Frame>
+    {
+        functions
+            .iter()
+            .map(|(function, internal)| {
+                let mut frame = Frame::new();
+                frame.function = Some(function.to_string());
+                frame.internal = *internal;
+                frame
+            })
+            .collect()
+    }
+
+    fn names(entries: Vec<StackEntry>) -> Vec<String>
+    {
+        entries
+            .into_iter()
+            .map(|entry| match entry {
+                StackEntry::Php(frame) => frame.name(),
+                StackEntry::Native(frame) => format!("[{}]", frame.symbol.as_deref().unwrap_or("")),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn native_only_stack()
+    {
+        let native = native_frames(&["nanosleep", "main"]);
+        assert_eq!(names(splice(&native, &[])), ["[nanosleep]", "[main]"]);
+    }
+
+    #[test]
+    fn php_frames_follow_execute_ex()
+    {
+        let native = native_frames(&["nanosleep", "zif_usleep", "execute_ex", "php_execute_script", "main"]);
+        let php = php_frames(&[("usleep", true), ("wait", false), ("run", false)]);
+        assert_eq!(
+            names(splice(&native, &php)),
+            ["[nanosleep]", "[zif_usleep]", "[execute_ex]", "usleep()", "wait()", "run()", "[php_execute_script]", "[main]"]
+        );
+    }
+
+    #[test]
+    fn callbacks_run_by_internal_functions()
+    {
+        // A user callback runs in its own `execute_ex`, above the call
+        let native = native_frames(&["execute_ex", "zend_call_function", "zif_array_map", "execute_ex", "main"]);
+        let php = php_frames(&[("callback", false), ("array_map", true), ("run", false)]);
+        assert_eq!(
+            names(splice(&native, &php)),
+            ["[execute_ex]", "callback()", "[zend_call_function]", "[zif_array_map]", "[execute_ex]", "array_map()", "run()", "[main]"]
+        );
+
+        // An internal one is called by `zend_call_function` directly
+        let native = native_frames(&["zif_strlen", "zend_call_function", "zif_call_user_func", "execute_ex", "main"]);
+        let php = php_frames(&[("strlen", true), ("call_user_func", true), ("run", false)]);
+        assert_eq!(
+            names(splice(&native, &php)),
+            ["[zif_strlen]", "[zend_call_function]", "strlen()", "[zif_call_user_func]", "[execute_ex]", "call_user_func()", "run()", "[main]"]
+        );
+    }
+
+    #[test]
+    fn php_frames_without_executor_are_appended()
+    {
+        let native = native_frames(&["nanosleep"]);
+        let php = php_frames(&[("usleep", true), ("run", false)]);
+        assert_eq!(names(splice(&native, &php)), ["[nanosleep]", "usleep()", "run()"]);
+    }
+
+    /// Frame records in a leaked buffer, each the saved frame pointer and
+    /// the return address: the next record and `ips[i + 1]`
+    fn frame_records(ips: &[usize]) -> &'static mut [usize]
+    {
+        let stack = Box::leak(vec![0usize; 2 * ips.len()].into_boxed_slice());
+        let base = stack.as_ptr() as usize;
+        for i in 0..ips.len() {
+            stack[2 * i] = base + 16 * (i + 1);
+            stack[2 * i + 1] = ips.get(i + 1).copied().unwrap_or(0);
+        }
+        stack
+    }
+
+    fn unwind_self(regs: Registers) -> Vec<usize>
+    {
+        let pid = std::process::id() as Pid;
+        let source = pid.try_into_process_handle().unwrap();
+        unwind(pid, &source, regs, &ModuleCache::default()).iter().map(|frame| frame.addr).collect()
+    }
+
+    #[test]
+    fn unwind_through_frame_pointers()
+    {
+        // Addresses no module is mapped at, unwound through frame pointers
+        let bp = frame_records(&[0x1000, 0x2001, 0x3001]).as_ptr() as usize;
+        let regs = Registers { ip: 0x1000, sp: bp - 16, bp, lr: 0, pac_mask: 0 };
+        assert_eq!(unwind_self(regs), [0x1000, 0x2001, 0x3001]);
+    }
+
+    #[test]
+    fn unwind_strips_pointer_authentication()
+    {
+        let bp = frame_records(&[0x1000, 0x2a00_0000_0000_2001]).as_ptr() as usize;
+        let regs = Registers { ip: 0x1000, sp: bp - 16, bp, lr: 0, pac_mask: 0xff00_0000_0000_0000 };
+        assert_eq!(unwind_self(regs), [0x1000, 0x2001]);
+    }
+
+    #[test]
+    fn unwind_stops_at_a_looping_frame_pointer()
+    {
+        let stack = frame_records(&[0x1000, 0x2001, 0x3001]);
+        let bp = stack.as_ptr() as usize;
+        // The second record points back at itself
+        stack[2] = bp + 16;
+        let regs = Registers { ip: 0x1000, sp: bp - 16, bp, lr: 0, pac_mask: 0 };
+        assert_eq!(unwind_self(regs), [0x1000, 0x2001, 0x3001]);
+    }
+}
@@ -23,6 +23,15 @@ pub struct _zend_object_value {
     pub handlers: *const zend_object_handlers,
 }
 pub type zend_object_value = _zend_object_value;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object {
+    pub ce: *mut zend_class_entry,
+    pub properties: *mut HashTable,
+    pub properties_table: *mut *mut zval,
+    pub guards: *mut HashTable,
+}
+pub type zend_object = _zend_object;
 pub type dtor_func_t =
     ::std::option::Option<unsafe extern "C" fn(pDest: *mut ::std::os::raw::c_void)>;
 #[repr(C)]
@@ -765,6 +774,21 @@ pub struct _zend_objects_store {
     pub free_list_head: ::std::os::raw::c_int,
 }
 pub type zend_objects_store = _zend_objects_store;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_generator_iterator {
+    pub intern: zend_object_iterator,
+    pub object: *mut zval,
+}
+pub type zend_generator_iterator = _zend_generator_iterator;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_generator {
+    pub std: zend_object,
+    pub iterator: zend_generator_iterator,
+    pub execute_data: *mut zend_execute_data,
+}
+pub type zend_generator = _zend_generator;
 pub type fpu_control_t = ::std::os::raw::c_ushort;
 pub type zend_vm_stack = *mut _zend_vm_stack;
 pub type zend_ini_entry = _zend_ini_entry;
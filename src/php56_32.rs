@@ -0,0 +1,765 @@
+/* php56.rs for ILP32 targets: pointers, `long` and `size_t` as 32-bit
+ * integers, 8-byte types 4-aligned as on i686 */
+
+#![allow(non_snake_case, non_camel_case_types, non_upper_case_globals, unused)]
+
+pub type ulong = u32;
+pub type uint = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __sigset_t {
+    pub __val: [u32; 32usize],
+}
+pub type zend_bool = ::std::os::raw::c_uchar;
+pub type zend_uchar = ::std::os::raw::c_uchar;
+pub type zend_uint = ::std::os::raw::c_uint;
+pub type zend_ulong = u32;
+pub type zend_object_handle = ::std::os::raw::c_uint;
+pub type zend_object_handlers = _zend_object_handlers;
+pub type zval = _zval_struct;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_value {
+    pub handle: zend_object_handle,
+    pub handlers: u32,
+}
+pub type zend_object_value = _zend_object_value;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object {
+    pub ce: u32,
+    pub properties: u32,
+    pub properties_table: u32,
+    pub guards: u32,
+}
+pub type zend_object = _zend_object;
+pub type dtor_func_t =
+    u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct bucket {
+    pub h: ulong,
+    pub nKeyLength: uint,
+    pub pData: u32,
+    pub pDataPtr: u32,
+    pub pListNext: u32,
+    pub pListLast: u32,
+    pub pNext: u32,
+    pub pLast: u32,
+    pub arKey: u32,
+}
+pub type Bucket = bucket;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _hashtable {
+    pub nTableSize: uint,
+    pub nTableMask: uint,
+    pub nNumOfElements: uint,
+    pub nNextFreeElement: ulong,
+    pub pInternalPointer: u32,
+    pub pListHead: u32,
+    pub pListTail: u32,
+    pub arBuckets: u32,
+    pub pDestructor: dtor_func_t,
+    pub persistent: zend_bool,
+    pub nApplyCount: ::std::os::raw::c_uchar,
+    pub bApplyProtection: zend_bool,
+}
+pub type HashTable = _hashtable;
+pub type zend_class_entry = _zend_class_entry;
+pub type zend_object_read_property_t = u32;
+pub type zend_object_read_dimension_t = u32;
+pub type zend_object_write_property_t = u32;
+pub type zend_object_write_dimension_t = u32;
+pub type zend_object_get_property_ptr_ptr_t = u32;
+pub type zend_object_set_t =
+    u32;
+pub type zend_object_get_t =
+    u32;
+pub type zend_object_has_property_t = u32;
+pub type zend_object_has_dimension_t = u32;
+pub type zend_object_unset_property_t = u32;
+pub type zend_object_unset_dimension_t =
+    u32;
+pub type zend_object_get_properties_t =
+    u32;
+pub type zend_object_get_debug_info_t = u32;
+pub type zend_object_call_method_t = u32;
+pub type zend_object_get_method_t = u32;
+pub type zend_object_get_constructor_t =
+    u32;
+pub type zend_object_add_ref_t = u32;
+pub type zend_object_del_ref_t = u32;
+pub type zend_object_clone_obj_t =
+    u32;
+pub type zend_object_get_class_entry_t =
+    u32;
+pub type zend_object_get_class_name_t = u32;
+pub type zend_object_compare_t = u32;
+pub type zend_object_compare_zvals_t = u32;
+pub type zend_object_cast_t = u32;
+pub type zend_object_count_elements_t = u32;
+pub type zend_object_get_closure_t = u32;
+pub type zend_object_get_gc_t = u32;
+pub type zend_object_do_operation_t = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_handlers {
+    pub add_ref: zend_object_add_ref_t,
+    pub del_ref: zend_object_del_ref_t,
+    pub clone_obj: zend_object_clone_obj_t,
+    pub read_property: zend_object_read_property_t,
+    pub write_property: zend_object_write_property_t,
+    pub read_dimension: zend_object_read_dimension_t,
+    pub write_dimension: zend_object_write_dimension_t,
+    pub get_property_ptr_ptr: zend_object_get_property_ptr_ptr_t,
+    pub get: zend_object_get_t,
+    pub set: zend_object_set_t,
+    pub has_property: zend_object_has_property_t,
+    pub unset_property: zend_object_unset_property_t,
+    pub has_dimension: zend_object_has_dimension_t,
+    pub unset_dimension: zend_object_unset_dimension_t,
+    pub get_properties: zend_object_get_properties_t,
+    pub get_method: zend_object_get_method_t,
+    pub call_method: zend_object_call_method_t,
+    pub get_constructor: zend_object_get_constructor_t,
+    pub get_class_entry: zend_object_get_class_entry_t,
+    pub get_class_name: zend_object_get_class_name_t,
+    pub compare_objects: zend_object_compare_t,
+    pub cast_object: zend_object_cast_t,
+    pub count_elements: zend_object_count_elements_t,
+    pub get_debug_info: zend_object_get_debug_info_t,
+    pub get_closure: zend_object_get_closure_t,
+    pub get_gc: zend_object_get_gc_t,
+    pub do_operation: zend_object_do_operation_t,
+    pub compare: zend_object_compare_zvals_t,
+}
+pub type zend_ast = _zend_ast;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_ast {
+    pub kind: ::std::os::raw::c_ushort,
+    pub children: ::std::os::raw::c_ushort,
+    pub u: _zend_ast__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_ast__bindgen_ty_1 {
+    pub val: u32,
+    pub child: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zvalue_value {
+    pub lval: i32,
+    pub dval: [u32; 2usize],
+    pub str: _zvalue_value__bindgen_ty_1,
+    pub ht: u32,
+    pub obj: zend_object_value,
+    pub ast: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zvalue_value__bindgen_ty_1 {
+    pub val: u32,
+    pub len: ::std::os::raw::c_int,
+}
+pub type zvalue_value = _zvalue_value;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zval_struct {
+    pub value: zvalue_value,
+    pub refcount__gc: zend_uint,
+    pub type_: zend_uchar,
+    pub is_ref__gc: zend_uchar,
+}
+pub type zend_object_iterator = _zend_object_iterator;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_iterator_funcs {
+    pub dtor: u32,
+    pub valid: u32,
+    pub get_current_data: u32,
+    pub get_current_key: u32,
+    pub move_forward: u32,
+    pub rewind: u32,
+    pub invalidate_current:
+        u32,
+}
+pub type zend_object_iterator_funcs = _zend_object_iterator_funcs;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_iterator {
+    pub data: u32,
+    pub funcs: u32,
+    pub index: ulong,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_iterator_funcs {
+    pub funcs: u32,
+    pub zf_new_iterator: u32,
+    pub zf_valid: u32,
+    pub zf_current: u32,
+    pub zf_key: u32,
+    pub zf_next: u32,
+    pub zf_rewind: u32,
+}
+pub type zend_class_iterator_funcs = _zend_class_iterator_funcs;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_serialize_data {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_unserialize_data {
+    _unused: [u8; 0],
+}
+pub type zend_serialize_data = _zend_serialize_data;
+pub type zend_unserialize_data = _zend_unserialize_data;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_method_reference {
+    pub method_name: u32,
+    pub mname_len: ::std::os::raw::c_uint,
+    pub ce: u32,
+    pub class_name: u32,
+    pub cname_len: ::std::os::raw::c_uint,
+}
+pub type zend_trait_method_reference = _zend_trait_method_reference;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_precedence {
+    pub trait_method: u32,
+    pub exclude_from_classes: u32,
+}
+pub type zend_trait_precedence = _zend_trait_precedence;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_alias {
+    pub trait_method: u32,
+    #[doc = " name for method to be added"]
+    pub alias: u32,
+    pub alias_len: ::std::os::raw::c_uint,
+    #[doc = " modifiers to be set on trait method"]
+    pub modifiers: zend_uint,
+}
+pub type zend_trait_alias = _zend_trait_alias;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_class_entry {
+    pub type_: ::std::os::raw::c_char,
+    pub name: u32,
+    pub name_length: zend_uint,
+    pub parent: u32,
+    pub refcount: ::std::os::raw::c_int,
+    pub ce_flags: zend_uint,
+    pub function_table: HashTable,
+    pub properties_info: HashTable,
+    pub default_properties_table: u32,
+    pub default_static_members_table: u32,
+    pub static_members_table: u32,
+    pub constants_table: HashTable,
+    pub default_properties_count: ::std::os::raw::c_int,
+    pub default_static_members_count: ::std::os::raw::c_int,
+    pub constructor: u32,
+    pub destructor: u32,
+    pub clone: u32,
+    pub __get: u32,
+    pub __set: u32,
+    pub __unset: u32,
+    pub __isset: u32,
+    pub __call: u32,
+    pub __callstatic: u32,
+    pub __tostring: u32,
+    pub __debugInfo: u32,
+    pub serialize_func: u32,
+    pub unserialize_func: u32,
+    pub iterator_funcs: zend_class_iterator_funcs,
+    pub create_object: u32,
+    pub get_iterator: u32,
+    pub interface_gets_implemented: u32,
+    pub get_static_method: u32,
+    pub serialize: u32,
+    pub unserialize: u32,
+    pub interfaces: u32,
+    pub num_interfaces: zend_uint,
+    pub traits: u32,
+    pub num_traits: zend_uint,
+    pub trait_aliases: u32,
+    pub trait_precedences: u32,
+    pub info: _zend_class_entry__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_class_entry__bindgen_ty_1 {
+    pub user: _zend_class_entry__bindgen_ty_1__bindgen_ty_1,
+    pub internal: _zend_class_entry__bindgen_ty_1__bindgen_ty_2,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_entry__bindgen_ty_1__bindgen_ty_1 {
+    pub filename: u32,
+    pub line_start: zend_uint,
+    pub line_end: zend_uint,
+    pub doc_comment: u32,
+    pub doc_comment_len: zend_uint,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_entry__bindgen_ty_1__bindgen_ty_2 {
+    pub builtin_functions: u32,
+    pub module: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _gc_root_buffer {
+    pub prev: u32,
+    pub next: u32,
+    pub handle: zend_object_handle,
+    pub u: _gc_root_buffer__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _gc_root_buffer__bindgen_ty_1 {
+    pub pz: u32,
+    pub handlers: u32,
+}
+pub type gc_root_buffer = _gc_root_buffer;
+pub const zend_error_handling_t_EH_NORMAL: zend_error_handling_t = 0;
+pub const zend_error_handling_t_EH_SUPPRESS: zend_error_handling_t = 1;
+pub const zend_error_handling_t_EH_THROW: zend_error_handling_t = 2;
+pub type zend_error_handling_t = u32;
+pub type zend_op_array = _zend_op_array;
+pub type zend_op = _zend_op;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_literal {
+    pub constant: zval,
+    pub hash_value: zend_ulong,
+    pub cache_slot: zend_uint,
+}
+pub type zend_literal = _zend_literal;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _znode_op {
+    pub constant: zend_uint,
+    pub var: zend_uint,
+    pub num: zend_uint,
+    pub hash: zend_ulong,
+    pub opline_num: zend_uint,
+    pub jmp_addr: u32,
+    pub zv: u32,
+    pub literal: u32,
+    pub ptr: u32,
+}
+pub type znode_op = _znode_op;
+pub type zend_execute_data = _zend_execute_data;
+pub type opcode_handler_t = u32;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_op {
+    pub handler: opcode_handler_t,
+    pub op1: znode_op,
+    pub op2: znode_op,
+    pub result: znode_op,
+    pub extended_value: ulong,
+    pub lineno: uint,
+    pub opcode: zend_uchar,
+    pub op1_type: zend_uchar,
+    pub op2_type: zend_uchar,
+    pub result_type: zend_uchar,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_brk_cont_element {
+    pub start: ::std::os::raw::c_int,
+    pub cont: ::std::os::raw::c_int,
+    pub brk: ::std::os::raw::c_int,
+    pub parent: ::std::os::raw::c_int,
+}
+pub type zend_brk_cont_element = _zend_brk_cont_element;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_try_catch_element {
+    pub try_op: zend_uint,
+    pub catch_op: zend_uint,
+    pub finally_op: zend_uint,
+    pub finally_end: zend_uint,
+}
+pub type zend_try_catch_element = _zend_try_catch_element;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_property_info {
+    pub flags: zend_uint,
+    pub name: u32,
+    pub name_length: ::std::os::raw::c_int,
+    pub h: ulong,
+    pub offset: ::std::os::raw::c_int,
+    pub doc_comment: u32,
+    pub doc_comment_len: ::std::os::raw::c_int,
+    pub ce: u32,
+}
+pub type zend_property_info = _zend_property_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_arg_info {
+    pub name: u32,
+    pub name_len: zend_uint,
+    pub class_name: u32,
+    pub class_name_len: zend_uint,
+    pub type_hint: zend_uchar,
+    pub pass_by_reference: zend_uchar,
+    pub allow_null: zend_bool,
+    pub is_variadic: zend_bool,
+}
+pub type zend_arg_info = _zend_arg_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_compiled_variable {
+    pub name: u32,
+    pub name_len: ::std::os::raw::c_int,
+    pub hash_value: ulong,
+}
+pub type zend_compiled_variable = _zend_compiled_variable;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_op_array {
+    pub type_: zend_uchar,
+    pub function_name: u32,
+    pub scope: u32,
+    pub fn_flags: zend_uint,
+    pub prototype: u32,
+    pub num_args: zend_uint,
+    pub required_num_args: zend_uint,
+    pub arg_info: u32,
+    pub refcount: u32,
+    pub opcodes: u32,
+    pub last: zend_uint,
+    pub vars: u32,
+    pub last_var: ::std::os::raw::c_int,
+    pub T: zend_uint,
+    pub nested_calls: zend_uint,
+    pub used_stack: zend_uint,
+    pub brk_cont_array: u32,
+    pub last_brk_cont: ::std::os::raw::c_int,
+    pub try_catch_array: u32,
+    pub last_try_catch: ::std::os::raw::c_int,
+    pub has_finally_block: zend_bool,
+    pub static_variables: u32,
+    pub this_var: zend_uint,
+    pub filename: u32,
+    pub line_start: zend_uint,
+    pub line_end: zend_uint,
+    pub doc_comment: u32,
+    pub doc_comment_len: zend_uint,
+    pub early_binding: zend_uint,
+    pub literals: u32,
+    pub last_literal: ::std::os::raw::c_int,
+    pub run_time_cache: u32,
+    pub last_cache_slot: ::std::os::raw::c_int,
+    pub reserved: [u32; 4usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_internal_function {
+    pub type_: zend_uchar,
+    pub function_name: u32,
+    pub scope: u32,
+    pub fn_flags: zend_uint,
+    pub prototype: u32,
+    pub num_args: zend_uint,
+    pub required_num_args: zend_uint,
+    pub arg_info: u32,
+    pub handler: u32,
+    pub module: u32,
+}
+pub type zend_internal_function = _zend_internal_function;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_function {
+    pub type_: zend_uchar,
+    pub common: _zend_function__bindgen_ty_1,
+    pub op_array: zend_op_array,
+    pub internal_function: zend_internal_function,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_function__bindgen_ty_1 {
+    pub type_: zend_uchar,
+    pub function_name: u32,
+    pub scope: u32,
+    pub fn_flags: zend_uint,
+    pub prototype: u32,
+    pub num_args: zend_uint,
+    pub required_num_args: zend_uint,
+    pub arg_info: u32,
+}
+pub type zend_function = _zend_function;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_function_state {
+    pub function: u32,
+    pub arguments: u32,
+}
+pub type zend_function_state = _zend_function_state;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _call_slot {
+    pub fbc: u32,
+    pub object: u32,
+    pub called_scope: u32,
+    pub num_additional_args: zend_uint,
+    pub is_ctor_call: zend_bool,
+    pub is_ctor_result_used: zend_bool,
+}
+pub type call_slot = _call_slot;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_execute_data {
+    pub opline: u32,
+    pub function_state: zend_function_state,
+    pub op_array: u32,
+    pub object: u32,
+    pub symbol_table: u32,
+    pub prev_execute_data: u32,
+    pub old_error_reporting: u32,
+    pub nested: zend_bool,
+    pub original_return_value: u32,
+    pub current_scope: u32,
+    pub current_called_scope: u32,
+    pub current_this: u32,
+    pub fast_ret: u32,
+    pub delayed_exception: u32,
+    pub call_slots: u32,
+    pub call: u32,
+}
+pub type __jmp_buf = [i32; 8usize];
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __jmp_buf_tag {
+    pub __jmpbuf: __jmp_buf,
+    pub __mask_was_saved: ::std::os::raw::c_int,
+    pub __saved_mask: __sigset_t,
+}
+pub type jmp_buf = [__jmp_buf_tag; 1usize];
+pub type zend_executor_globals = _zend_executor_globals;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_stack {
+    pub top: ::std::os::raw::c_int,
+    pub max: ::std::os::raw::c_int,
+    pub elements: u32,
+}
+pub type zend_stack = _zend_stack;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_ptr_stack {
+    pub top: ::std::os::raw::c_int,
+    pub max: ::std::os::raw::c_int,
+    pub elements: u32,
+    pub top_element: u32,
+    pub persistent: zend_bool,
+}
+pub type zend_ptr_stack = _zend_ptr_stack;
+pub type zend_objects_store_dtor_t = u32;
+pub type zend_objects_free_object_storage_t =
+    u32;
+pub type zend_objects_store_clone_t = u32;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_object_store_bucket {
+    pub destructor_called: zend_bool,
+    pub valid: zend_bool,
+    pub apply_count: zend_uchar,
+    pub bucket: _zend_object_store_bucket__store_bucket,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_object_store_bucket__store_bucket {
+    pub obj: _zend_object_store_bucket__store_bucket__store_object,
+    pub free_list: _zend_object_store_bucket__store_bucket__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_store_bucket__store_bucket__store_object {
+    pub object: u32,
+    pub dtor: zend_objects_store_dtor_t,
+    pub free_storage: zend_objects_free_object_storage_t,
+    pub clone: zend_objects_store_clone_t,
+    pub handlers: u32,
+    pub refcount: zend_uint,
+    pub buffered: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_store_bucket__store_bucket__bindgen_ty_1 {
+    pub next: ::std::os::raw::c_int,
+}
+pub type zend_object_store_bucket = _zend_object_store_bucket;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_objects_store {
+    pub object_buckets: u32,
+    pub top: zend_uint,
+    pub size: zend_uint,
+    pub free_list_head: ::std::os::raw::c_int,
+}
+pub type zend_objects_store = _zend_objects_store;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_generator_iterator {
+    pub intern: zend_object_iterator,
+    pub object: u32,
+}
+pub type zend_generator_iterator = _zend_generator_iterator;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_generator {
+    pub std: zend_object,
+    pub iterator: zend_generator_iterator,
+    pub execute_data: u32,
+}
+pub type zend_generator = _zend_generator;
+pub type fpu_control_t = ::std::os::raw::c_ushort;
+pub type zend_vm_stack = u32;
+pub type zend_ini_entry = _zend_ini_entry;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_executor_globals {
+    pub return_value_ptr_ptr: u32,
+    pub uninitialized_zval: zval,
+    pub uninitialized_zval_ptr: u32,
+    pub error_zval: zval,
+    pub error_zval_ptr: u32,
+    pub symtable_cache: [u32; 32usize],
+    pub symtable_cache_limit: u32,
+    pub symtable_cache_ptr: u32,
+    pub opline_ptr: u32,
+    pub active_symbol_table: u32,
+    pub symbol_table: HashTable,
+    pub included_files: HashTable,
+    pub bailout: u32,
+    pub error_reporting: ::std::os::raw::c_int,
+    pub orig_error_reporting: ::std::os::raw::c_int,
+    pub exit_status: ::std::os::raw::c_int,
+    pub active_op_array: u32,
+    pub function_table: u32,
+    pub class_table: u32,
+    pub zend_constants: u32,
+    pub scope: u32,
+    pub called_scope: u32,
+    pub This: u32,
+    pub precision: i32,
+    pub ticks_count: ::std::os::raw::c_int,
+    pub in_execution: zend_bool,
+    pub in_autoload: u32,
+    pub autoload_func: u32,
+    pub full_tables_cleanup: zend_bool,
+    pub no_extensions: zend_bool,
+    pub regular_list: HashTable,
+    pub persistent_list: HashTable,
+    pub argument_stack: zend_vm_stack,
+    pub user_error_handler_error_reporting: ::std::os::raw::c_int,
+    pub user_error_handler: u32,
+    pub user_exception_handler: u32,
+    pub user_error_handlers_error_reporting: zend_stack,
+    pub user_error_handlers: zend_ptr_stack,
+    pub user_exception_handlers: zend_ptr_stack,
+    pub error_handling: zend_error_handling_t,
+    pub exception_class: u32,
+    pub timeout_seconds: ::std::os::raw::c_int,
+    pub lambda_count: ::std::os::raw::c_int,
+    pub ini_directives: u32,
+    pub modified_ini_directives: u32,
+    pub error_reporting_ini_entry: u32,
+    pub objects_store: zend_objects_store,
+    pub exception: u32,
+    pub prev_exception: u32,
+    pub opline_before_exception: u32,
+    pub exception_op: [zend_op; 3usize],
+    pub current_execute_data: u32,
+    pub current_module: u32,
+    pub std_property_info: zend_property_info,
+    pub active: zend_bool,
+    pub start_op: u32,
+    pub saved_fpu_cw_ptr: u32,
+    pub saved_fpu_cw: fpu_control_t,
+    pub reserved: [u32; 4usize],
+}
+pub type zend_module_entry = _zend_module_entry;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_module_entry {
+    pub size: ::std::os::raw::c_ushort,
+    pub zend_api: ::std::os::raw::c_uint,
+    pub zend_debug: ::std::os::raw::c_uchar,
+    pub zts: ::std::os::raw::c_uchar,
+    pub ini_entry: u32,
+    pub deps: u32,
+    pub name: u32,
+    pub functions: u32,
+    pub module_startup_func: u32,
+    pub module_shutdown_func: u32,
+    pub request_startup_func: u32,
+    pub request_shutdown_func: u32,
+    pub info_func: u32,
+    pub version: u32,
+    pub globals_size: u32,
+    pub globals_ptr: u32,
+    pub globals_ctor:
+        u32,
+    pub globals_dtor:
+        u32,
+    pub post_deactivate_func:
+        u32,
+    pub module_started: ::std::os::raw::c_int,
+    pub type_: ::std::os::raw::c_uchar,
+    pub handle: u32,
+    pub module_number: ::std::os::raw::c_int,
+    pub build_id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_module_dep {
+    pub name: u32,
+    pub rel: u32,
+    pub version: u32,
+    pub type_: ::std::os::raw::c_uchar,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_vm_stack {
+    pub top: u32,
+    pub end: u32,
+    pub prev: zend_vm_stack,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_function_entry {
+    pub fname: u32,
+    pub handler: u32,
+    pub arg_info: u32,
+    pub num_args: zend_uint,
+    pub flags: zend_uint,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_ini_entry {
+    pub module_number: ::std::os::raw::c_int,
+    pub modifiable: ::std::os::raw::c_int,
+    pub name: u32,
+    pub name_length: uint,
+    pub on_modify: u32,
+    pub mh_arg1: u32,
+    pub mh_arg2: u32,
+    pub mh_arg3: u32,
+    pub value: u32,
+    pub value_length: uint,
+    pub orig_value: u32,
+    pub orig_value_length: uint,
+    pub orig_modifiable: ::std::os::raw::c_int,
+    pub modified: ::std::os::raw::c_int,
+    pub displayer: u32,
+}
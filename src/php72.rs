@@ -742,6 +742,15 @@ pub struct _zend_objects_store {
     pub free_list_head: ::std::os::raw::c_int,
 }
 pub type zend_objects_store = _zend_objects_store;
+pub type zend_generator = _zend_generator;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_generator {
+    pub std: zend_object,
+    pub iterator: *mut zend_object_iterator,
+    pub execute_data: *mut zend_execute_data,
+    pub frozen_call_stack: *mut zend_execute_data,
+}
 pub type fpu_control_t = ::std::os::raw::c_ushort;
 pub type zend_vm_stack = *mut _zend_vm_stack;
 pub type zend_ini_entry = _zend_ini_entry;
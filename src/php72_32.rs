@@ -0,0 +1,748 @@
+/* php72.rs for ILP32 targets: pointers, `long` and `size_t` as 32-bit
+ * integers, 8-byte types 4-aligned as on i686 */
+
+#![allow(non_snake_case, non_camel_case_types, non_upper_case_globals, unused)]
+
+pub type __uint16_t = ::std::os::raw::c_ushort;
+pub type __uint32_t = ::std::os::raw::c_uint;
+pub type __int64_t = [u32; 2usize];
+pub type __uint64_t = [u32; 2usize];
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __sigset_t {
+    pub __val: [u32; 32usize],
+}
+pub type zend_long = i32;
+pub type zend_ulong = u32;
+pub type zend_bool = ::std::os::raw::c_uchar;
+pub type zend_uchar = ::std::os::raw::c_uchar;
+pub type zend_object_handlers = _zend_object_handlers;
+pub type zend_class_entry = _zend_class_entry;
+pub type zend_function = _zend_function;
+pub type zend_execute_data = _zend_execute_data;
+pub type zval = _zval_struct;
+pub type zend_refcounted = _zend_refcounted;
+pub type zend_string = _zend_string;
+pub type zend_array = _zend_array;
+pub type zend_object = _zend_object;
+pub type zend_resource = _zend_resource;
+pub type zend_reference = _zend_reference;
+pub type zend_ast_ref = _zend_ast_ref;
+pub type zend_ast = _zend_ast;
+pub type dtor_func_t = u32;
+pub type zend_type = u32;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_value {
+    pub lval: zend_long,
+    pub dval: [u32; 2usize],
+    pub counted: u32,
+    pub str: u32,
+    pub arr: u32,
+    pub obj: u32,
+    pub res: u32,
+    pub ref_: u32,
+    pub ast: u32,
+    pub zv: u32,
+    pub ptr: u32,
+    pub ce: u32,
+    pub func: u32,
+    pub ww: _zend_value__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_value__bindgen_ty_1 {
+    pub w1: u32,
+    pub w2: u32,
+}
+pub type zend_value = _zend_value;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zval_struct {
+    pub value: zend_value,
+    pub u1: _zval_struct__bindgen_ty_1,
+    pub u2: _zval_struct__bindgen_ty_2,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zval_struct__bindgen_ty_1 {
+    pub v: _zval_struct__bindgen_ty_1__bindgen_ty_1,
+    pub type_info: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zval_struct__bindgen_ty_1__bindgen_ty_1 {
+    pub type_: zend_uchar,
+    pub type_flags: zend_uchar,
+    pub const_flags: zend_uchar,
+    pub reserved: zend_uchar,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zval_struct__bindgen_ty_2 {
+    pub next: u32,
+    pub cache_slot: u32,
+    pub lineno: u32,
+    pub num_args: u32,
+    pub fe_pos: u32,
+    pub fe_iter_idx: u32,
+    pub access_flags: u32,
+    pub property_guard: u32,
+    pub extra: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_refcounted_h {
+    pub refcount: u32,
+    pub u: _zend_refcounted_h__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_refcounted_h__bindgen_ty_1 {
+    pub v: _zend_refcounted_h__bindgen_ty_1__bindgen_ty_1,
+    pub type_info: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_refcounted_h__bindgen_ty_1__bindgen_ty_1 {
+    pub type_: zend_uchar,
+    pub flags: zend_uchar,
+    pub gc_info: u16,
+}
+pub type zend_refcounted_h = _zend_refcounted_h;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_refcounted {
+    pub gc: zend_refcounted_h,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_string {
+    pub gc: zend_refcounted_h,
+    pub h: zend_ulong,
+    pub len: u32,
+    pub val: [::std::os::raw::c_char; 1usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _Bucket {
+    pub val: zval,
+    pub h: zend_ulong,
+    pub key: u32,
+}
+pub type Bucket = _Bucket;
+pub type HashTable = _zend_array;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_array {
+    pub gc: zend_refcounted_h,
+    pub u: _zend_array__bindgen_ty_1,
+    pub nTableMask: u32,
+    pub arData: u32,
+    pub nNumUsed: u32,
+    pub nNumOfElements: u32,
+    pub nTableSize: u32,
+    pub nInternalPointer: u32,
+    pub nNextFreeElement: zend_long,
+    pub pDestructor: dtor_func_t,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_array__bindgen_ty_1 {
+    pub v: _zend_array__bindgen_ty_1__bindgen_ty_1,
+    pub flags: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_array__bindgen_ty_1__bindgen_ty_1 {
+    pub flags: zend_uchar,
+    pub nApplyCount: zend_uchar,
+    pub nIteratorsCount: zend_uchar,
+    pub consistency: zend_uchar,
+}
+pub type HashPosition = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _HashTableIterator {
+    pub ht: u32,
+    pub pos: HashPosition,
+}
+pub type HashTableIterator = _HashTableIterator;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_object {
+    pub gc: zend_refcounted_h,
+    pub handle: u32,
+    pub ce: u32,
+    pub handlers: u32,
+    pub properties: u32,
+    pub properties_table: [zval; 1usize],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_resource {
+    pub gc: zend_refcounted_h,
+    pub handle: ::std::os::raw::c_int,
+    pub type_: ::std::os::raw::c_int,
+    pub ptr: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_reference {
+    pub gc: zend_refcounted_h,
+    pub val: zval,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_ast_ref {
+    pub gc: zend_refcounted_h,
+    pub ast: u32,
+}
+pub type zend_ast_kind = u16;
+pub type zend_ast_attr = u16;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_ast {
+    pub kind: zend_ast_kind,
+    pub attr: zend_ast_attr,
+    pub lineno: u32,
+    pub child: [u32; 1usize],
+}
+pub type zend_object_iterator = _zend_object_iterator;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_iterator_funcs {
+    pub dtor: u32,
+    pub valid: u32,
+    pub get_current_data:
+        u32,
+    pub get_current_key: u32,
+    pub move_forward: u32,
+    pub rewind: u32,
+    pub invalidate_current:
+        u32,
+}
+pub type zend_object_iterator_funcs = _zend_object_iterator_funcs;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_object_iterator {
+    pub std: zend_object,
+    pub data: zval,
+    pub funcs: u32,
+    pub index: zend_ulong,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_iterator_funcs {
+    pub funcs: u32,
+    pub zf_new_iterator: u32,
+    pub zf_valid: u32,
+    pub zf_current: u32,
+    pub zf_key: u32,
+    pub zf_next: u32,
+    pub zf_rewind: u32,
+}
+pub type zend_class_iterator_funcs = _zend_class_iterator_funcs;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_serialize_data {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_unserialize_data {
+    _unused: [u8; 0],
+}
+pub type zend_serialize_data = _zend_serialize_data;
+pub type zend_unserialize_data = _zend_unserialize_data;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_method_reference {
+    pub method_name: u32,
+    pub ce: u32,
+    pub class_name: u32,
+}
+pub type zend_trait_method_reference = _zend_trait_method_reference;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_precedence {
+    pub trait_method: u32,
+    pub exclude_from_classes: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_trait_precedence__bindgen_ty_1 {
+    pub ce: u32,
+    pub class_name: u32,
+}
+pub type zend_trait_precedence = _zend_trait_precedence;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_trait_alias {
+    pub trait_method: u32,
+    #[doc = " name for method to be added"]
+    pub alias: u32,
+    #[doc = " modifiers to be set on trait method"]
+    pub modifiers: u32,
+}
+pub type zend_trait_alias = _zend_trait_alias;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_class_entry {
+    pub type_: ::std::os::raw::c_char,
+    pub name: u32,
+    pub parent: u32,
+    pub refcount: ::std::os::raw::c_int,
+    pub ce_flags: u32,
+    pub default_properties_count: ::std::os::raw::c_int,
+    pub default_static_members_count: ::std::os::raw::c_int,
+    pub default_properties_table: u32,
+    pub default_static_members_table: u32,
+    pub static_members_table: u32,
+    pub function_table: HashTable,
+    pub properties_info: HashTable,
+    pub constants_table: HashTable,
+    pub constructor: u32,
+    pub destructor: u32,
+    pub clone: u32,
+    pub __get: u32,
+    pub __set: u32,
+    pub __unset: u32,
+    pub __isset: u32,
+    pub __call: u32,
+    pub __callstatic: u32,
+    pub __tostring: u32,
+    pub __debugInfo: u32,
+    pub serialize_func: u32,
+    pub unserialize_func: u32,
+    pub iterator_funcs: zend_class_iterator_funcs,
+    pub create_object: u32,
+    pub get_iterator: u32,
+    pub interface_gets_implemented: u32,
+    pub get_static_method: u32,
+    pub serialize: u32,
+    pub unserialize: u32,
+    pub num_interfaces: u32,
+    pub num_traits: u32,
+    pub interfaces: u32,
+    pub traits: u32,
+    pub trait_aliases: u32,
+    pub trait_precedences: u32,
+    pub info: _zend_class_entry__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_class_entry__bindgen_ty_1 {
+    pub user: _zend_class_entry__bindgen_ty_1__bindgen_ty_1,
+    pub internal: _zend_class_entry__bindgen_ty_1__bindgen_ty_2,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_entry__bindgen_ty_1__bindgen_ty_1 {
+    pub filename: u32,
+    pub line_start: u32,
+    pub line_end: u32,
+    pub doc_comment: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_class_entry__bindgen_ty_1__bindgen_ty_2 {
+    pub builtin_functions: u32,
+    pub module: u32,
+}
+pub const zend_error_handling_t_EH_NORMAL: zend_error_handling_t = 0;
+pub const zend_error_handling_t_EH_SUPPRESS: zend_error_handling_t = 1;
+pub const zend_error_handling_t_EH_THROW: zend_error_handling_t = 2;
+pub type zend_error_handling_t = u32;
+pub type zend_object_read_property_t = u32;
+pub type zend_object_read_dimension_t = u32;
+pub type zend_object_write_property_t = u32;
+pub type zend_object_write_dimension_t = u32;
+pub type zend_object_get_property_ptr_ptr_t = u32;
+pub type zend_object_set_t =
+    u32;
+pub type zend_object_get_t =
+    u32;
+pub type zend_object_has_property_t = u32;
+pub type zend_object_has_dimension_t = u32;
+pub type zend_object_unset_property_t = u32;
+pub type zend_object_unset_dimension_t =
+    u32;
+pub type zend_object_get_properties_t =
+    u32;
+pub type zend_object_get_debug_info_t = u32;
+pub type zend_object_call_method_t = u32;
+pub type zend_object_get_method_t = u32;
+pub type zend_object_get_constructor_t =
+    u32;
+pub type zend_object_dtor_obj_t =
+    u32;
+pub type zend_object_free_obj_t =
+    u32;
+pub type zend_object_clone_obj_t =
+    u32;
+pub type zend_object_get_class_name_t =
+    u32;
+pub type zend_object_compare_t = u32;
+pub type zend_object_compare_zvals_t = u32;
+pub type zend_object_cast_t = u32;
+pub type zend_object_count_elements_t = u32;
+pub type zend_object_get_closure_t = u32;
+pub type zend_object_get_gc_t = u32;
+pub type zend_object_do_operation_t = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_object_handlers {
+    pub offset: ::std::os::raw::c_int,
+    pub free_obj: zend_object_free_obj_t,
+    pub dtor_obj: zend_object_dtor_obj_t,
+    pub clone_obj: zend_object_clone_obj_t,
+    pub read_property: zend_object_read_property_t,
+    pub write_property: zend_object_write_property_t,
+    pub read_dimension: zend_object_read_dimension_t,
+    pub write_dimension: zend_object_write_dimension_t,
+    pub get_property_ptr_ptr: zend_object_get_property_ptr_ptr_t,
+    pub get: zend_object_get_t,
+    pub set: zend_object_set_t,
+    pub has_property: zend_object_has_property_t,
+    pub unset_property: zend_object_unset_property_t,
+    pub has_dimension: zend_object_has_dimension_t,
+    pub unset_dimension: zend_object_unset_dimension_t,
+    pub get_properties: zend_object_get_properties_t,
+    pub get_method: zend_object_get_method_t,
+    pub call_method: zend_object_call_method_t,
+    pub get_constructor: zend_object_get_constructor_t,
+    pub get_class_name: zend_object_get_class_name_t,
+    pub compare_objects: zend_object_compare_t,
+    pub cast_object: zend_object_cast_t,
+    pub count_elements: zend_object_count_elements_t,
+    pub get_debug_info: zend_object_get_debug_info_t,
+    pub get_closure: zend_object_get_closure_t,
+    pub get_gc: zend_object_get_gc_t,
+    pub do_operation: zend_object_do_operation_t,
+    pub compare: zend_object_compare_zvals_t,
+}
+pub type zend_op_array = _zend_op_array;
+pub type zend_op = _zend_op;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _znode_op {
+    pub constant: u32,
+    pub var: u32,
+    pub num: u32,
+    pub opline_num: u32,
+    pub jmp_offset: u32,
+}
+pub type znode_op = _znode_op;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_op {
+    pub handler: u32,
+    pub op1: znode_op,
+    pub op2: znode_op,
+    pub result: znode_op,
+    pub extended_value: u32,
+    pub lineno: u32,
+    pub opcode: zend_uchar,
+    pub op1_type: zend_uchar,
+    pub op2_type: zend_uchar,
+    pub result_type: zend_uchar,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_try_catch_element {
+    pub try_op: u32,
+    pub catch_op: u32,
+    pub finally_op: u32,
+    pub finally_end: u32,
+}
+pub type zend_try_catch_element = _zend_try_catch_element;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_live_range {
+    pub var: u32,
+    pub start: u32,
+    pub end: u32,
+}
+pub type zend_live_range = _zend_live_range;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_internal_arg_info {
+    pub name: u32,
+    pub type_: zend_type,
+    pub pass_by_reference: zend_uchar,
+    pub is_variadic: zend_bool,
+}
+pub type zend_internal_arg_info = _zend_internal_arg_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_arg_info {
+    pub name: u32,
+    pub type_: zend_type,
+    pub pass_by_reference: zend_uchar,
+    pub is_variadic: zend_bool,
+}
+pub type zend_arg_info = _zend_arg_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_op_array {
+    pub type_: zend_uchar,
+    pub arg_flags: [zend_uchar; 3usize],
+    pub fn_flags: u32,
+    pub function_name: u32,
+    pub scope: u32,
+    pub prototype: u32,
+    pub num_args: u32,
+    pub required_num_args: u32,
+    pub arg_info: u32,
+    pub refcount: u32,
+    pub last: u32,
+    pub opcodes: u32,
+    pub last_var: ::std::os::raw::c_int,
+    pub T: u32,
+    pub vars: u32,
+    pub last_live_range: ::std::os::raw::c_int,
+    pub last_try_catch: ::std::os::raw::c_int,
+    pub live_range: u32,
+    pub try_catch_array: u32,
+    pub static_variables: u32,
+    pub filename: u32,
+    pub line_start: u32,
+    pub line_end: u32,
+    pub doc_comment: u32,
+    pub early_binding: u32,
+    pub last_literal: ::std::os::raw::c_int,
+    pub literals: u32,
+    pub cache_size: ::std::os::raw::c_int,
+    pub run_time_cache: u32,
+    pub reserved: [u32; 6usize],
+}
+pub type zif_handler = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_internal_function {
+    pub type_: zend_uchar,
+    pub arg_flags: [zend_uchar; 3usize],
+    pub fn_flags: u32,
+    pub function_name: u32,
+    pub scope: u32,
+    pub prototype: u32,
+    pub num_args: u32,
+    pub required_num_args: u32,
+    pub arg_info: u32,
+    pub handler: zif_handler,
+    pub module: u32,
+    pub reserved: [u32; 6usize],
+}
+pub type zend_internal_function = _zend_internal_function;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union _zend_function {
+    pub type_: zend_uchar,
+    pub quick_arg_flags: u32,
+    pub common: _zend_function__bindgen_ty_1,
+    pub op_array: zend_op_array,
+    pub internal_function: zend_internal_function,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_function__bindgen_ty_1 {
+    pub type_: zend_uchar,
+    pub arg_flags: [zend_uchar; 3usize],
+    pub fn_flags: u32,
+    pub function_name: u32,
+    pub scope: u32,
+    pub prototype: u32,
+    pub num_args: u32,
+    pub required_num_args: u32,
+    pub arg_info: u32,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_execute_data {
+    pub opline: u32,
+    pub call: u32,
+    pub return_value: u32,
+    pub func: u32,
+    pub This: zval,
+    pub prev_execute_data: u32,
+    pub symbol_table: u32,
+    pub run_time_cache: u32,
+}
+pub type __jmp_buf = [i32; 8usize];
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __jmp_buf_tag {
+    pub __jmpbuf: __jmp_buf,
+    pub __mask_was_saved: ::std::os::raw::c_int,
+    pub __saved_mask: __sigset_t,
+}
+pub type jmp_buf = [__jmp_buf_tag; 1usize];
+pub type zend_executor_globals = _zend_executor_globals;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_stack {
+    pub size: ::std::os::raw::c_int,
+    pub top: ::std::os::raw::c_int,
+    pub max: ::std::os::raw::c_int,
+    pub elements: u32,
+}
+pub type zend_stack = _zend_stack;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_objects_store {
+    pub object_buckets: u32,
+    pub top: u32,
+    pub size: u32,
+    pub free_list_head: ::std::os::raw::c_int,
+}
+pub type zend_objects_store = _zend_objects_store;
+pub type zend_generator = _zend_generator;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_generator {
+    pub std: zend_object,
+    pub iterator: u32,
+    pub execute_data: u32,
+    pub frozen_call_stack: u32,
+}
+pub type fpu_control_t = ::std::os::raw::c_ushort;
+pub type zend_vm_stack = u32;
+pub type zend_ini_entry = _zend_ini_entry;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct _zend_executor_globals {
+    pub uninitialized_zval: zval,
+    pub error_zval: zval,
+    pub symtable_cache: [u32; 32usize],
+    pub symtable_cache_limit: u32,
+    pub symtable_cache_ptr: u32,
+    pub symbol_table: zend_array,
+    pub included_files: HashTable,
+    pub bailout: u32,
+    pub error_reporting: ::std::os::raw::c_int,
+    pub exit_status: ::std::os::raw::c_int,
+    pub function_table: u32,
+    pub class_table: u32,
+    pub zend_constants: u32,
+    pub vm_stack_top: u32,
+    pub vm_stack_end: u32,
+    pub vm_stack: zend_vm_stack,
+    pub current_execute_data: u32,
+    pub fake_scope: u32,
+    pub precision: zend_long,
+    pub ticks_count: ::std::os::raw::c_int,
+    pub in_autoload: u32,
+    pub autoload_func: u32,
+    pub full_tables_cleanup: zend_bool,
+    pub no_extensions: zend_bool,
+    pub vm_interrupt: zend_bool,
+    pub timed_out: zend_bool,
+    pub hard_timeout: zend_long,
+    pub regular_list: HashTable,
+    pub persistent_list: HashTable,
+    pub user_error_handler_error_reporting: ::std::os::raw::c_int,
+    pub user_error_handler: zval,
+    pub user_exception_handler: zval,
+    pub user_error_handlers_error_reporting: zend_stack,
+    pub user_error_handlers: zend_stack,
+    pub user_exception_handlers: zend_stack,
+    pub error_handling: zend_error_handling_t,
+    pub exception_class: u32,
+    pub timeout_seconds: zend_long,
+    pub lambda_count: ::std::os::raw::c_int,
+    pub ini_directives: u32,
+    pub modified_ini_directives: u32,
+    pub error_reporting_ini_entry: u32,
+    pub objects_store: zend_objects_store,
+    pub exception: u32,
+    pub prev_exception: u32,
+    pub opline_before_exception: u32,
+    pub exception_op: [zend_op; 3usize],
+    pub current_module: u32,
+    pub active: zend_bool,
+    pub flags: zend_uchar,
+    pub assertions: zend_long,
+    pub ht_iterators_count: u32,
+    pub ht_iterators_used: u32,
+    pub ht_iterators: u32,
+    pub ht_iterators_slots: [HashTableIterator; 16usize],
+    pub saved_fpu_cw_ptr: u32,
+    pub saved_fpu_cw: fpu_control_t,
+    pub trampoline: zend_function,
+    pub call_trampoline_op: zend_op,
+    pub each_deprecation_thrown: zend_bool,
+    pub reserved: [u32; 6usize],
+}
+pub type zend_module_entry = _zend_module_entry;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_module_entry {
+    pub size: ::std::os::raw::c_ushort,
+    pub zend_api: ::std::os::raw::c_uint,
+    pub zend_debug: ::std::os::raw::c_uchar,
+    pub zts: ::std::os::raw::c_uchar,
+    pub ini_entry: u32,
+    pub deps: u32,
+    pub name: u32,
+    pub functions: u32,
+    pub module_startup_func: u32,
+    pub module_shutdown_func: u32,
+    pub request_startup_func: u32,
+    pub request_shutdown_func: u32,
+    pub info_func: u32,
+    pub version: u32,
+    pub globals_size: u32,
+    pub globals_ptr: u32,
+    pub globals_ctor:
+        u32,
+    pub globals_dtor:
+        u32,
+    pub post_deactivate_func:
+        u32,
+    pub module_started: ::std::os::raw::c_int,
+    pub type_: ::std::os::raw::c_uchar,
+    pub handle: u32,
+    pub module_number: ::std::os::raw::c_int,
+    pub build_id: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_module_dep {
+    pub name: u32,
+    pub rel: u32,
+    pub version: u32,
+    pub type_: ::std::os::raw::c_uchar,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_vm_stack {
+    pub top: u32,
+    pub end: u32,
+    pub prev: zend_vm_stack,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_function_entry {
+    pub fname: u32,
+    pub handler: zif_handler,
+    pub arg_info: u32,
+    pub num_args: u32,
+    pub flags: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_ini_entry {
+    pub name: u32,
+    pub on_modify: u32,
+    pub mh_arg1: u32,
+    pub mh_arg2: u32,
+    pub mh_arg3: u32,
+    pub value: u32,
+    pub orig_value: u32,
+    pub displayer: u32,
+    pub modifiable: ::std::os::raw::c_int,
+    pub orig_modifiable: ::std::os::raw::c_int,
+    pub modified: ::std::os::raw::c_int,
+    pub module_number: ::std::os::raw::c_int,
+}
@@ -1,246 +1,1214 @@
-#[allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#![allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
 
 extern crate read_process_memory;
 
-use crate::php73;
-use crate::php72;
-use crate::php56;
+use crate::layout::{Engine, Layout, Php5Layout};
+use crate::opcodes;
+use crate::native::{self, NativeFrame, StackEntry};
+use crate::sapi::{self, Request};
+use crate::syscall::Blocked;
+
+use crate::error::{Error, Result};
 
 use read_process_memory::{copy_address, ProcessHandle};
-use std::mem::{size_of, transmute};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem::size_of;
+use std::ptr;
+
+/// zval types of PHP 7
+const IS_UNDEF: u8 = 0;
+const IS_NULL: u8 = 1;
+const IS_FALSE: u8 = 2;
+const IS_TRUE: u8 = 3;
+const IS_LONG: u8 = 4;
+const IS_DOUBLE: u8 = 5;
+const IS_STRING: u8 = 6;
+const IS_ARRAY: u8 = 7;
+const IS_OBJECT: u8 = 8;
+const IS_RESOURCE: u8 = 9;
+const IS_REFERENCE: u8 = 10;
+
+/// zval types of PHP 5
+const PHP5_IS_NULL: u8 = 0;
+const PHP5_IS_LONG: u8 = 1;
+const PHP5_IS_DOUBLE: u8 = 2;
+const PHP5_IS_BOOL: u8 = 3;
+const PHP5_IS_ARRAY: u8 = 4;
+const PHP5_IS_OBJECT: u8 = 5;
+const PHP5_IS_STRING: u8 = 6;
+const PHP5_IS_RESOURCE: u8 = 7;
+
+/// Arguments read per frame, and bytes read per string argument
+const MAX_ARGS: usize = 32;
+const MAX_ARG_STRING: usize = 64;
+
+/// Generators followed through `yield from`
+const MAX_DELEGATION: usize = 64;
+
+/// Bytes read of names and file names, a torn string may claim any length
+const MAX_STRING: usize = 4096;
+
+/// Frames read of a stack, a torn `prev_execute_data` chain may loop
+const MAX_DEPTH: usize = 4096;
+
+/// Handles in the objects store beyond which `top` is taken for garbage
+const MAX_OBJECTS: usize = 1 << 20;
+
+const ZEND_INTERNAL_FUNCTION: u8 = 1;
+const ZEND_USER_FUNCTION: u8 = 2;
+
+const ZEND_INCLUDE_OR_EVAL: u8 = 73;
+
+const ZEND_EVAL: u32 = 1 << 0;
+const ZEND_INCLUDE: u32 = 1 << 1;
+const ZEND_INCLUDE_ONCE: u32 = 1 << 2;
+const ZEND_REQUIRE: u32 = 1 << 3;
+const ZEND_REQUIRE_ONCE: u32 = 1 << 4;
+
+/// Low bit set on `EG(objects_store)` buckets that are free
+const OBJ_BUCKET_INVALID: usize = 1;
 
 pub trait ProcessReader {
-    fn read(&self, addr: usize) -> Trace;
+    fn read(&self, addr: usize) -> Result<Trace>;
+
+    /// Suspended generators found in `EG(objects_store)`
+    fn read_coroutines(&self, addr: usize) -> Result<Vec<Coroutine>>;
+
+    /// Whether any PHP code is running, without walking the stack
+    fn is_executing(&self, addr: usize) -> Result<bool>;
+
+    /// Arguments of each frame returned by `read`, formatted like
+    /// `debug_print_backtrace()`
+    fn read_args(&self, addr: usize) -> Result<Vec<Vec<String>>>;
+
+    /// Whether `addr` validates as `executor_globals`
+    fn is_executor_globals(&self, addr: usize) -> bool;
+
+    /// Request in the `sapi_globals` at `addr`, read without stopping the
+    /// process. `None` if the memory can't be read, e.g. the process exited.
+    fn read_request(&self, addr: usize) -> Option<Request>;
+
+    /// Address of something that validates as `executor_globals` between
+    /// `start` and `end`, for executables without the symbol
+    fn find_executor_globals(&self, start: usize, end: usize) -> Option<usize>;
 }
 
 pub struct Trace {
+    pub frames: Vec<Frame>,
+    pub coroutines: Vec<Coroutine>,
+    /// Native frames of the stopped thread, empty unless requested
+    pub native: Vec<NativeFrame>,
+}
+
+pub struct Coroutine {
+    pub class: String,
+    /// Object handle in the objects store
+    pub handle: u32,
+    pub frames: Vec<Frame>,
+}
 
+pub enum FrameKind {
+    Function,
+    /// Top-level script
+    Main,
+    /// File entered through `include`, `require` or their `_once` variants
+    Include(&'static str),
+    /// Code compiled by `eval()`
+    Eval,
 }
 
-pub struct PHP730 { pub source: ProcessHandle }
-pub struct PHP720 { pub source: ProcessHandle }
-pub struct PHP560 { pub source: ProcessHandle }
+pub struct Frame {
+    pub kind: FrameKind,
+    /// Function name, `None` for top-level code of a file
+    pub function: Option<String>,
+    /// Class declaring the function (`func.common.scope`)
+    pub scope: Option<String>,
+    /// Class of `$this`, or the late-static-bound called scope
+    pub class: Option<String>,
+    /// Source file of the op_array, for top-level code and closures
+    pub file: Option<String>,
+    /// Opcode being executed, only set on the topmost user frame
+    pub opcode: Option<&'static str>,
+    /// Function implemented in C
+    pub internal: bool,
+    /// Syscall the process sleeps in, only set on a topmost internal frame
+    pub blocked: Option<Blocked>,
+    /// Call arguments, only set when requested
+    pub args: Option<Vec<String>>,
+}
 
-impl PHP730 {
+impl Frame {
+    pub(crate) fn new() -> Frame
+    {
+        Frame {
+            kind: FrameKind::Function,
+            function: None,
+            scope: None,
+            class: None,
+            file: None,
+            opcode: None,
+            internal: false,
+            blocked: None,
+            args: None,
+        }
+    }
 
-    fn get_executor_global(&self, addr: usize) -> php73::zend_executor_globals
+    /// Qualified function name, without annotations
+    pub fn name(&self) -> String
     {
-        read_memory::<php73::zend_executor_globals>(&self.source, addr)
+        match (&self.kind, &self.function) {
+            (FrameKind::Eval, _) => "eval()'d code".to_string(),
+            (FrameKind::Include(kind), _) => format!("{}({})", kind, self.file.as_deref().unwrap_or("")),
+            (FrameKind::Function, Some(name)) => match (&self.class, &self.scope) {
+                (Some(class), Some(scope)) if class != scope => format!("{} ({})::{}()", class, scope, name),
+                (_, Some(scope)) | (Some(scope), None) => format!("{}::{}()", scope, name),
+                (None, None) => format!("{}()", name),
+            },
+            _ => "main()".to_string(),
+        }
     }
+}
 
-    fn get_execute_data(&self, addr: usize) -> php73::zend_execute_data
+#[cfg(test)]
+impl Trace {
+    /// Stack of plain functions, leaf first
+    pub(crate) fn of_functions(names: &[&str]) -> Trace
     {
-        read_memory::<php73::zend_execute_data>(&self.source, addr)
+        let frames = names
+            .iter()
+            .map(|name| Frame { function: Some(name.to_string()), ..Frame::new() })
+            .collect();
+        Trace { frames, coroutines: Vec::new(), native: Vec::new() }
     }
+}
 
-    fn get_function(&self, addr: usize) -> php73::zend_function
+impl Default for Frame {
+    fn default() -> Frame
     {
-        read_memory::<php73::zend_function>(&self.source, addr)
+        Frame::new()
     }
+}
 
-    fn get_string(&self, addr: usize) -> String
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
-        let zend_str = read_memory::<php73::zend_string>(&self.source, addr);
-        let offset = unsafe { &(*(::std::ptr::null::<php73::zend_string>())).val as *const _ as usize };
+        let name = self.name();
+        match &self.args {
+            Some(args) if name.ends_with("()") => write!(f, "{}({})", &name[..name.len() - 2], args.join(", "))?,
+            _ => write!(f, "{}", name)?,
+        }
+
+        if let Some(opcode) = self.opcode {
+            write!(f, "  [{}]", opcode)?;
+        }
+        if let Some(blocked) = &self.blocked {
+            write!(f, "  [{}]", blocked)?;
+        }
+        Ok(())
+    }
+}
 
-        let val = copy_address(addr + offset, zend_str.len, &self.source).unwrap();
-        unsafe { String::from_utf8_unchecked(val) }
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        if self.native.is_empty() {
+            for frame in &self.frames {
+                writeln!(f, "{}", frame)?;
+            }
+        } else {
+            for entry in native::splice(&self.native, &self.frames) {
+                match entry {
+                    StackEntry::Php(frame) => writeln!(f, "{}", frame)?,
+                    StackEntry::Native(frame) => writeln!(f, "{}", frame)?,
+                }
+            }
+        }
+        for coroutine in &self.coroutines {
+            writeln!(f)?;
+            writeln!(f, "{} #{}", coroutine.class, coroutine.handle)?;
+            for frame in &coroutine.frames {
+                writeln!(f, "{}", frame)?;
+            }
+        }
+        Ok(())
     }
 }
 
-impl ProcessReader for PHP730 {
+/// Reads the stack of any supported version, the differences between them
+/// described by its layout
+pub struct Walker {
+    pub source: ProcessHandle,
+    pub layout: Layout,
+}
+
+/// Fields of `executor_globals`
+struct ExecutorGlobals {
+    function_table: usize,
+    class_table: usize,
+    zend_constants: usize,
+    current_execute_data: usize,
+    precision: i64,
+    object_buckets: usize,
+    objects_top: u32,
+}
+
+/// Fields of a `zend_execute_data`
+struct ExecuteData {
+    opline: usize,
+    func: usize,
+    prev_execute_data: usize,
+    /// PHP 7: type and value of the `This` zval, the object of a method call
+    /// or the called scope (`EX(called_scope)` in 7.0), and the number of
+    /// arguments
+    this_type: u8,
+    this_value: usize,
+    num_args: u32,
+    /// PHP 5: the `$this` zval, the active call slot or, in 5.4, the called
+    /// scope, and the arguments
+    object: usize,
+    call: usize,
+    called_scope: usize,
+    arguments: usize,
+}
+
+/// Fields of a `zend_function`, those of `op_array` only meaningful for user
+/// functions
+struct Function {
+    type_: u8,
+    function_name: usize,
+    scope: usize,
+    filename: usize,
+    line_start: u32,
+    num_args: u32,
+    last_var: u32,
+    t: u32,
+}
 
-    fn read(&self, addr: usize) -> Trace
+impl Walker {
+    pub fn new(source: ProcessHandle, layout: Layout) -> Walker
     {
-        let eg = self.get_executor_global(addr);
-        let mut ex_addr = eg.current_execute_data as usize;
+        Walker { source, layout }
+    }
 
-        let mut output = String::new();
+    /// Pointer or `size_t` at `offset` of a copied structure, as wide as
+    /// the process has them
+    fn word(&self, bytes: &[u8], offset: usize) -> Result<usize>
+    {
+        Ok(match self.layout.word {
+            4 => field::<u32>(bytes, offset)? as usize,
+            _ => field::<u64>(bytes, offset)? as usize,
+        })
+    }
 
-        while ex_addr != 0 {
-            let ex = self.get_execute_data(ex_addr);
+    /// `zend_long`, or `long` in PHP 5
+    fn long(&self, bytes: &[u8], offset: usize) -> Result<i64>
+    {
+        Ok(match self.layout.word {
+            4 => field::<i32>(bytes, offset)? as i64,
+            _ => field::<i64>(bytes, offset)?,
+        })
+    }
 
-            let func_addr = ex.func as usize;
-            if func_addr == 0 {
-                break
-            };
+    fn read_word(&self, addr: usize) -> Result<usize>
+    {
+        self.word(&copy(&self.source, addr, self.layout.word)?, 0)
+    }
 
-            let func = self.get_function(func_addr);
+    fn read_words(&self, addr: usize, count: usize) -> Result<Vec<usize>>
+    {
+        let bytes = copy(&self.source, addr, count * self.layout.word)?;
+        bytes.chunks_exact(self.layout.word).map(|word| self.word(word, 0)).collect()
+    }
 
-            unsafe {
-                if func.common.scope as usize != 0 {
-                    let ce = read_memory::<php73::zend_class_entry>(&self.source, func.common.scope as usize);
-                    let scope = self.get_string(ce.name as usize);
-                    output = output + &scope + "::";
-                }
+    fn get_executor_global(&self, addr: usize) -> Result<ExecutorGlobals>
+    {
+        let eg = &self.layout.executor_globals;
+        let bytes = copy(&self.source, addr, eg.size)?;
+        self.parse_executor_globals(&bytes)
+    }
+
+    fn parse_executor_globals(&self, bytes: &[u8]) -> Result<ExecutorGlobals>
+    {
+        let eg = &self.layout.executor_globals;
+        Ok(ExecutorGlobals {
+            function_table: self.word(bytes, eg.function_table)?,
+            class_table: self.word(bytes, eg.class_table)?,
+            zend_constants: self.word(bytes, eg.zend_constants)?,
+            current_execute_data: self.word(bytes, eg.current_execute_data)?,
+            precision: self.long(bytes, eg.precision)?,
+            object_buckets: self.word(bytes, eg.object_buckets)?,
+            objects_top: field(bytes, eg.objects_top)?,
+        })
+    }
+
+    /// A sane `precision` ini setting and distinct function, class and
+    /// constant tables
+    fn is_valid_executor_globals(&self, eg: &ExecutorGlobals) -> bool
+    {
+        (-1..=64).contains(&eg.precision)
+            && eg.function_table != eg.class_table
+            && self.is_hash_table(eg.function_table)
+            && self.is_hash_table(eg.class_table)
+            && self.is_hash_table(eg.zend_constants)
+    }
+
+    /// Whether `addr` holds an initialized, non-empty hash table
+    fn is_hash_table(&self, addr: usize) -> bool
+    {
+        self.check_hash_table(addr).unwrap_or(false)
+    }
+
+    fn check_hash_table(&self, addr: usize) -> Result<bool>
+    {
+        let ht = &self.layout.hash_table;
+        let bytes = copy(&self.source, addr, ht.size)?;
+        let table_size: u32 = field(&bytes, ht.table_size)?;
+        let table_mask: u32 = field(&bytes, ht.table_mask)?;
+        let num_of_elements: u32 = field(&bytes, ht.num_of_elements)?;
+        let data = self.word(&bytes, ht.data)?;
+
+        let consistent = match &self.layout.engine {
+            Engine::Php7(php7) => {
+                let num_used: u32 = field(&bytes, php7.hash_num_used)?;
+                // The hash part is twice the table size since 7.3
+                (table_mask == table_size.wrapping_neg() || table_mask == table_size.wrapping_mul(2).wrapping_neg())
+                    && num_of_elements <= num_used
+                    && num_used <= table_size
             }
+            Engine::Php5(_) => table_mask == table_size.wrapping_sub(1),
+        };
+        Ok(table_size.is_power_of_two() && consistent && num_of_elements > 0 && data != 0)
+    }
 
-            let function_name_addr = unsafe { func.common.function_name as usize };
-            if function_name_addr == 0 {
-                output = output + "main()";
-            } else {
-                let name = self.get_string(function_name_addr);
-                output = output + &name + "()\n";
+    fn get_execute_data(&self, addr: usize) -> Result<ExecuteData>
+    {
+        let ex = &self.layout.execute_data;
+        let bytes = copy(&self.source, addr, ex.size)?;
+        let mut execute_data = ExecuteData {
+            opline: self.word(&bytes, ex.opline)?,
+            func: self.word(&bytes, ex.func)?,
+            prev_execute_data: self.word(&bytes, ex.prev_execute_data)?,
+            this_type: 0,
+            this_value: 0,
+            num_args: 0,
+            object: 0,
+            call: 0,
+            called_scope: 0,
+            arguments: 0,
+        };
+        match &self.layout.engine {
+            Engine::Php7(php7) => {
+                execute_data.this_type = field(&bytes, php7.this + self.layout.zval.type_)?;
+                execute_data.this_value = self.word(&bytes, php7.this)?;
+                execute_data.num_args = field(&bytes, php7.this + php7.zval_u2)?;
+                if let Some(called_scope) = php7.called_scope {
+                    if execute_data.this_type != IS_OBJECT {
+                        execute_data.this_value = self.word(&bytes, called_scope)?;
+                    }
+                }
             }
+            Engine::Php5(php5) => {
+                execute_data.object = self.word(&bytes, php5.object)?;
+                if let Some(call) = php5.call {
+                    execute_data.call = self.word(&bytes, call)?;
+                }
+                if let Some(called_scope) = php5.called_scope {
+                    execute_data.called_scope = self.word(&bytes, called_scope)?;
+                }
+                execute_data.arguments = self.word(&bytes, php5.arguments)?;
+            }
+        }
+        Ok(execute_data)
+    }
+
+    fn get_function(&self, addr: usize) -> Result<Function>
+    {
+        let func = &self.layout.function;
+        let bytes = copy(&self.source, addr, func.size)?;
+        Ok(Function {
+            type_: field(&bytes, func.type_)?,
+            function_name: self.word(&bytes, func.function_name)?,
+            scope: self.word(&bytes, func.scope)?,
+            filename: self.word(&bytes, func.filename)?,
+            line_start: field(&bytes, func.line_start)?,
+            num_args: field(&bytes, func.num_args)?,
+            last_var: field(&bytes, func.last_var)?,
+            t: field(&bytes, func.t)?,
+        })
+    }
 
-            ex_addr = ex.prev_execute_data as usize;
+    /// A `zend_string`, or a C string in PHP 5
+    fn get_string(&self, addr: usize) -> Result<String>
+    {
+        match &self.layout.engine {
+            Engine::Php7(_) => {
+                let (val, _) = self.get_zend_string(addr, MAX_STRING)?;
+                Ok(unsafe { String::from_utf8_unchecked(val) })
+            }
+            Engine::Php5(_) => read_cstr(&self.source, addr),
         }
-        println!("{}", output);
-        Trace{}
     }
-}
 
-impl PHP720 {
-    fn get_executor_global(&self, addr: usize) -> php72::zend_executor_globals
+    /// At most `max` bytes of a PHP 7 `zend_string`, and its length
+    fn get_zend_string(&self, addr: usize, max: usize) -> Result<(Vec<u8>, usize)>
+    {
+        let string = match &self.layout.engine {
+            Engine::Php7(php7) => &php7.string,
+            Engine::Php5(_) => return Err(Error::Layout("no zend_string in PHP 5".to_string())),
+        };
+        let len = self.read_word(addr + string.len)?;
+        Ok((copy(&self.source, addr + string.val, len.min(max))?, len))
+    }
+
+    fn get_class_name(&self, addr: usize) -> Result<String>
     {
-        read_memory::<php72::zend_executor_globals>(&self.source, addr)
+        let name = self.read_word(addr + self.layout.class_entry.name)?;
+        self.get_string(name)
     }
 
-    fn get_execute_data(&self, addr: usize) -> php72::zend_execute_data
+    fn get_object_class(&self, obj_addr: usize) -> Result<String>
     {
-        read_memory::<php72::zend_execute_data>(&self.source, addr)
+        let ce = self.read_word(obj_addr + self.layout.object.ce)?;
+        self.get_class_name(ce)
     }
 
-    fn get_function(&self, addr: usize) -> php72::zend_function
+    /// Object of a PHP 5 handle, looked up in the objects store
+    fn get_object_by_handle(&self, eg: &ExecutorGlobals, php5: &Php5Layout, handle: usize) -> Result<usize>
     {
-        read_memory::<php72::zend_function>(&self.source, addr)
+        let bucket_addr = eg.object_buckets + handle * php5.bucket_size;
+        self.read_word(bucket_addr + php5.bucket_object)
     }
 
-    fn get_string(&self, addr: usize) -> String
+    /// Class of `$this` for method calls, otherwise the called scope: of
+    /// `EX(This)` in PHP 7, of `EX(object)` or the active call slot in PHP 5
+    fn get_this_class(&self, eg: &ExecutorGlobals, ex: &ExecuteData) -> Result<Option<String>>
     {
-        let zend_str = read_memory::<php72::zend_string>(&self.source, addr);
-        let offset = unsafe { &(*(::std::ptr::null::<php72::zend_string>())).val as *const _ as usize };
+        match &self.layout.engine {
+            Engine::Php7(_) => {
+                if ex.this_type == IS_OBJECT {
+                    Ok(Some(self.get_object_class(ex.this_value)?))
+                } else if ex.this_value != 0 {
+                    Ok(Some(self.get_class_name(ex.this_value)?))
+                } else {
+                    Ok(None)
+                }
+            }
+            Engine::Php5(php5) => {
+                if ex.object != 0 {
+                    let this = copy(&self.source, ex.object, self.layout.zval.size)?;
+                    if field::<u8>(&this, self.layout.zval.type_)? == PHP5_IS_OBJECT {
+                        let handle = field::<u32>(&this, 0)? as usize;
+                        return Ok(Some(self.get_object_class(self.get_object_by_handle(eg, php5, handle)?)?));
+                    }
+                }
 
-        let val = copy_address(addr + offset, zend_str.len, &self.source).unwrap();
-        unsafe { String::from_utf8_unchecked(val) }
+                let called_scope = if ex.call != 0 {
+                    self.read_word(ex.call + php5.call_called_scope)?
+                } else {
+                    ex.called_scope
+                };
+                if called_scope != 0 {
+                    return Ok(Some(self.get_class_name(called_scope)?));
+                }
+                Ok(None)
+            }
+        }
     }
-}
 
-impl ProcessReader for PHP720 {
+    /// Opcode and extended value of an opline
+    fn get_op(&self, addr: usize) -> Result<(u8, u32)>
+    {
+        let op = &self.layout.op;
+        Ok((
+            read_memory::<u8>(&self.source, addr + op.opcode)?,
+            read_memory::<u32>(&self.source, addr + op.extended_value)?,
+        ))
+    }
 
-    fn read(&self, addr: usize) -> Trace
+    /// Kind of a frame without function name, told by the opline its caller
+    /// is executing
+    fn get_include_kind(&self, prev_addr: usize) -> Result<FrameKind>
     {
-        let eg = self.get_executor_global(addr);
-        let mut ex_addr = eg.current_execute_data as usize;
+        if prev_addr == 0 {
+            return Ok(FrameKind::Main);
+        }
 
-        let mut output = String::new();
+        let prev = self.get_execute_data(prev_addr)?;
+        if prev.func == 0 || prev.opline == 0 {
+            return Ok(FrameKind::Main);
+        }
 
-        while ex_addr != 0 {
-            let ex = self.get_execute_data(ex_addr);
+        let func = self.get_function(prev.func)?;
+        if func.type_ != ZEND_USER_FUNCTION {
+            return Ok(FrameKind::Main);
+        }
 
-            let func_addr = ex.func as usize;
-            if func_addr == 0 {
-                break
-            };
+        let (opcode, extended_value) = self.get_op(prev.opline)?;
+        Ok(include_kind(opcode, extended_value))
+    }
 
-            let func = self.get_function(func_addr);
+    fn read_frames(&self, eg: &ExecutorGlobals, mut ex_addr: usize) -> Result<Vec<Frame>>
+    {
+        let mut frames = Vec::new();
 
-            unsafe {
-                if func.common.scope as usize != 0 {
-                    let ce = read_memory::<php73::zend_class_entry>(&self.source, func.common.scope as usize);
-                    let scope = self.get_string(ce.name as usize);
-                    output = output + &scope + "::";
-                }
+        while ex_addr != 0 && frames.len() < MAX_DEPTH {
+            let ex = self.get_execute_data(ex_addr)?;
+
+            match self.read_frame(eg, &ex)? {
+                Some(frame) => frames.push(frame),
+                None => break,
             }
+            ex_addr = ex.prev_execute_data;
+        }
+        Ok(frames)
+    }
+
+    fn read_frame(&self, eg: &ExecutorGlobals, ex: &ExecuteData) -> Result<Option<Frame>>
+    {
+        if ex.func == 0 {
+            return Ok(None);
+        }
 
-            let function_name_addr = unsafe { func.common.function_name as usize };
-            if function_name_addr == 0 {
-                output = output + "main()";
+        let func = self.get_function(ex.func)?;
+
+        let mut frame = Frame::new();
+        frame.internal = func.type_ == ZEND_INTERNAL_FUNCTION;
+
+        if func.scope != 0 {
+            frame.scope = Some(self.get_class_name(func.scope)?);
+            frame.class = self.get_this_class(eg, ex)?;
+        }
+
+        if func.function_name != 0 {
+            let name = self.get_string(func.function_name)?;
+            if name == "{closure}" && func.type_ == ZEND_USER_FUNCTION {
+                let file = self.get_string(func.filename)?;
+                frame.function = Some(format!("{{closure:{}:{}}}", file, func.line_start));
+                frame.file = Some(file);
             } else {
-                let name = self.get_string(function_name_addr);
-                output = output + &name + "()\n";
+                frame.function = Some(name);
             }
-            ex_addr = ex.prev_execute_data as usize;
+        } else {
+            if func.filename != 0 {
+                frame.file = Some(self.get_string(func.filename)?);
+            }
+            frame.kind = self.get_include_kind(ex.prev_execute_data)?;
         }
-        println!("{}", output);
-        Trace{}
+
+        Ok(Some(frame))
     }
-}
 
-impl PHP560 {
+    /// Short form of the zval at `addr`, strings truncated
+    fn format_zval(&self, eg: &ExecutorGlobals, addr: usize) -> Result<String>
+    {
+        self.format_value(eg, addr, false)
+    }
 
-    fn get_executor_global(&self, addr: usize) -> php56::zend_executor_globals
+    /// A reference never holds another one, so `in_reference` stops a torn
+    /// zval from being followed forever
+    fn format_value(&self, eg: &ExecutorGlobals, addr: usize, in_reference: bool) -> Result<String>
     {
-        read_memory::<php56::zend_executor_globals>(&self.source, addr)
+        let zval = copy(&self.source, addr, self.layout.zval.size)?;
+        let type_: u8 = field(&zval, self.layout.zval.type_)?;
+        let pointer = self.word(&zval, 0)?;
+
+        Ok(match &self.layout.engine {
+            Engine::Php7(php7) => match type_ {
+                IS_UNDEF | IS_NULL => "NULL".to_string(),
+                IS_FALSE => "false".to_string(),
+                IS_TRUE => "true".to_string(),
+                IS_LONG => self.long(&zval, 0)?.to_string(),
+                IS_DOUBLE => format!("{:?}", field::<f64>(&zval, 0)?),
+                IS_STRING => {
+                    let (val, len) = self.get_zend_string(pointer, MAX_ARG_STRING)?;
+                    format_string(&val, len)
+                }
+                IS_ARRAY => {
+                    let count = read_memory::<u32>(&self.source, pointer + self.layout.hash_table.num_of_elements)?;
+                    format!("Array({})", count)
+                }
+                IS_OBJECT => format!("Object({})", self.get_object_class(pointer)?),
+                IS_RESOURCE => {
+                    let handle = read_memory::<i32>(&self.source, pointer + php7.resource_handle)?;
+                    format!("Resource id #{}", handle)
+                }
+                IS_REFERENCE if !in_reference => self.format_value(eg, pointer + php7.reference_val, true)?,
+                _ => "?".to_string(),
+            },
+            Engine::Php5(php5) => match type_ {
+                PHP5_IS_NULL => "NULL".to_string(),
+                PHP5_IS_BOOL => (self.long(&zval, 0)? != 0).to_string(),
+                PHP5_IS_LONG => self.long(&zval, 0)?.to_string(),
+                PHP5_IS_DOUBLE => format!("{:?}", field::<f64>(&zval, 0)?),
+                PHP5_IS_STRING => {
+                    let len = field::<i32>(&zval, php5.zval_str_len)?.max(0) as usize;
+                    let val = copy(&self.source, pointer, len.min(MAX_ARG_STRING))?;
+                    format_string(&val, len)
+                }
+                PHP5_IS_ARRAY => {
+                    let count = read_memory::<u32>(&self.source, pointer + self.layout.hash_table.num_of_elements)?;
+                    format!("Array({})", count)
+                }
+                PHP5_IS_OBJECT => {
+                    let handle = field::<u32>(&zval, 0)? as usize;
+                    format!("Object({})", self.get_object_class(self.get_object_by_handle(eg, php5, handle)?)?)
+                }
+                PHP5_IS_RESOURCE => format!("Resource id #{}", self.long(&zval, 0)?),
+                _ => "?".to_string(),
+            },
+        })
     }
 
-    fn get_execute_data(&self, addr: usize) -> php56::zend_execute_data
+    /// Arguments passed to a frame.
+    ///
+    /// In PHP 7 they live in the slots following the execute_data; for user
+    /// functions, those beyond the declared parameters are moved after the
+    /// compiled and temporary variables.
+    ///
+    /// In PHP 5, `function_state.arguments` points at the argument count
+    /// pushed after the `zval *` of each argument; a user function running
+    /// its own code finds them in the function state of its caller.
+    fn read_frame_args(&self, eg: &ExecutorGlobals, ex_addr: usize, ex: &ExecuteData) -> Result<Vec<String>>
     {
-        read_memory::<php56::zend_execute_data>(&self.source, addr)
+        let zval_size = self.layout.zval.size;
+        let addrs: Vec<usize> = match &self.layout.engine {
+            Engine::Php7(_) => {
+                let slot = self.layout.execute_data.size.div_ceil(zval_size);
+                let args_addr = ex_addr + slot * zval_size;
+                let num_args = (ex.num_args as usize).min(MAX_ARGS);
+
+                let func = self.get_function(ex.func)?;
+                let declared = if func.type_ == ZEND_USER_FUNCTION { num_args.min(func.num_args as usize) } else { num_args };
+                let extra_addr = args_addr + (func.last_var as usize + func.t as usize) * zval_size;
+                (0..declared)
+                    .map(|i| args_addr + i * zval_size)
+                    .chain((0..num_args - declared).map(|i| extra_addr + i * zval_size))
+                    .collect()
+            }
+            Engine::Php5(_) => {
+                let mut arguments = ex.arguments;
+                if arguments == 0 && ex.prev_execute_data != 0 {
+                    arguments = self.get_execute_data(ex.prev_execute_data)?.arguments;
+                }
+                if arguments == 0 {
+                    return Ok(Vec::new());
+                }
+
+                // A stale `arguments` may point at anything
+                let num_args = self.read_word(arguments)?;
+                let start = num_args
+                    .checked_mul(self.layout.word)
+                    .and_then(|size| arguments.checked_sub(size))
+                    .ok_or_else(|| Error::Layout(format!("{} arguments below {:#x}", num_args, arguments)))?;
+                self.read_words(start, num_args.min(MAX_ARGS))?
+            }
+        };
+
+        addrs.into_iter().map(|addr| self.format_zval(eg, addr)).collect()
     }
 
-    fn get_function(&self, addr: usize) -> php56::zend_function
+    /// A generator followed by the generators it delegates to with
+    /// `yield from`, outermost first. `None` for generators other generators
+    /// delegate to, which are part of the chains of those.
+    fn read_delegation_chain(&self, obj_addr: usize) -> Result<Option<Vec<usize>>>
     {
-        read_memory::<php56::zend_function>(&self.source, addr)
+        let mut chain = vec![obj_addr];
+        let node = match &self.layout.engine {
+            Engine::Php7(php7) => php7.generator_node,
+            // No delegation in PHP 5
+            Engine::Php5(_) => return Ok(Some(chain)),
+        };
+
+        let children: u32 = read_memory(&self.source, obj_addr + node + self.layout.word)?;
+        if children != 0 {
+            return Ok(None);
+        }
+        loop {
+            let parent = self.read_word(chain[chain.len() - 1] + node)?;
+            if parent == 0 || chain.len() >= MAX_DELEGATION || chain.contains(&parent) {
+                return Ok(Some(chain));
+            }
+            chain.push(parent);
+        }
     }
 
-    fn get_string(&self, addr: usize) -> String
+    /// Addresses of the frames on the active call stack
+    fn read_execute_data_chain(&self, mut ex_addr: usize) -> Result<Vec<usize>>
     {
-        read_cstr(&self.source, addr)
+        let mut chain = Vec::new();
+        while ex_addr != 0 && chain.len() < MAX_DEPTH {
+            chain.push(ex_addr);
+            ex_addr = self.get_execute_data(ex_addr)?.prev_execute_data;
+        }
+        Ok(chain)
+    }
+
+    /// Depth and opcode of the topmost user frame's opline
+    fn get_current_opcode(&self, mut ex_addr: usize) -> Result<Option<(usize, u8)>>
+    {
+        let mut depth = 0;
+        while ex_addr != 0 && depth < MAX_DEPTH {
+            let ex = self.get_execute_data(ex_addr)?;
+            if ex.func == 0 {
+                return Ok(None);
+            }
+
+            let func = self.get_function(ex.func)?;
+            if func.type_ == ZEND_USER_FUNCTION && ex.opline != 0 {
+                let (opcode, _) = self.get_op(ex.opline)?;
+                return Ok(Some((depth, opcode)));
+            }
+
+            depth += 1;
+            ex_addr = ex.prev_execute_data;
+        }
+        Ok(None)
+    }
+
+    /// Handles and addresses of the objects in the objects store
+    fn read_objects(&self, eg: &ExecutorGlobals) -> Result<Vec<(usize, usize)>>
+    {
+        let top = eg.objects_top as usize;
+        if top > MAX_OBJECTS {
+            return Err(Error::Layout(format!("{} objects in the store", top)));
+        }
+        let objects: Vec<(usize, usize)> = match &self.layout.engine {
+            // Pointers, with the low bit set on free slots
+            Engine::Php7(_) => self.read_words(eg.object_buckets, top)?
+                .into_iter()
+                .enumerate()
+                .filter(|(_, obj_addr)| *obj_addr != 0 && obj_addr & OBJ_BUCKET_INVALID == 0)
+                .collect(),
+            Engine::Php5(php5) => {
+                let buckets = copy(&self.source, eg.object_buckets, top * php5.bucket_size)?;
+                let mut objects = Vec::new();
+                for (handle, bucket) in buckets.chunks_exact(php5.bucket_size).enumerate() {
+                    let obj_addr = self.word(bucket, php5.bucket_object)?;
+                    if field::<u8>(bucket, php5.bucket_valid)? != 0 && obj_addr != 0 {
+                        objects.push((handle, obj_addr));
+                    }
+                }
+                objects
+            }
+        };
+        // Handle 0 is never used
+        Ok(objects.into_iter().filter(|(handle, _)| *handle != 0).collect())
     }
 }
 
-impl ProcessReader for PHP560 {
+impl ProcessReader for Walker {
 
-    fn read(&self, addr: usize) -> Trace
+    fn read(&self, addr: usize) -> Result<Trace>
     {
-        let eg = self.get_executor_global(addr);
-        let mut ex_addr = eg.current_execute_data as usize;
+        let eg = self.get_executor_global(addr)?;
+        let mut frames = self.read_frames(&eg, eg.current_execute_data)?;
+        if let Some((depth, opcode)) = self.get_current_opcode(eg.current_execute_data)? {
+            frames[depth].opcode = opcodes::name(self.layout.opcodes, opcode);
+        }
+        Ok(Trace { frames, coroutines: Vec::new(), native: Vec::new() })
+    }
 
-        let mut output = String::new();
+    fn read_coroutines(&self, addr: usize) -> Result<Vec<Coroutine>>
+    {
+        let eg = self.get_executor_global(addr)?;
+        let running = self.read_execute_data_chain(eg.current_execute_data)?;
 
-        while ex_addr != 0 {
-            let ex = self.get_execute_data(ex_addr);
+        let mut class_names = HashMap::new();
+        let mut coroutines = Vec::new();
 
-            let func_addr = ex.function_state.function as usize;
-            if func_addr == 0 {
-                break
+        for (handle, obj_addr) in self.read_objects(&eg)? {
+            let ce = self.read_word(obj_addr + self.layout.object.ce)?;
+            let class = match class_names.entry(ce) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.get_class_name(ce)?),
             };
+            // Final, generators are never of another class
+            if class != "Generator" {
+                continue;
+            }
 
-            let func = self.get_function(func_addr);
+            let chain = match self.read_delegation_chain(obj_addr)? {
+                Some(chain) => chain,
+                None => continue,
+            };
 
-            unsafe {
-                if func.common.scope as usize != 0 {
-                    let ce = read_memory::<php56::zend_class_entry>(&self.source, func.common.scope as usize);
-                    let scope = read_cstr(&self.source, ce.name as usize);
-                    output = output + &scope + "::";
+            // Innermost generator first, as the stack is when it runs. Only
+            // the own frame of each generator is read: the caller it last
+            // ran from is left in `prev_execute_data`, and may be gone.
+            let mut frames = Vec::new();
+            let mut suspended = true;
+            for generator in chain.iter().rev() {
+                let ex_addr = self.read_word(generator + self.layout.generator.execute_data)?;
+                // Finished generators have no frame
+                if ex_addr == 0 {
+                    continue;
                 }
+                suspended &= !running.contains(&ex_addr);
+                frames.extend(self.read_frame(&eg, &self.get_execute_data(ex_addr)?)?);
+            }
+            if !suspended || frames.is_empty() {
+                continue;
             }
 
-            let function_name_addr = unsafe { func.common.function_name as usize };
-            if function_name_addr == 0 {
-                output = output + "main()";
-            } else {
-                let name = self.get_string(function_name_addr);
-                output = output + &name + "()\n";
+            coroutines.push(Coroutine {
+                class: class.clone(),
+                handle: handle as u32,
+                frames,
+            });
+        }
+        Ok(coroutines)
+    }
+
+    fn is_executing(&self, addr: usize) -> Result<bool>
+    {
+        Ok(self.get_executor_global(addr)?.current_execute_data != 0)
+    }
+
+    fn read_args(&self, addr: usize) -> Result<Vec<Vec<String>>>
+    {
+        let eg = self.get_executor_global(addr)?;
+        let mut args = Vec::new();
+        let mut ex_addr = eg.current_execute_data;
+        while ex_addr != 0 && args.len() < MAX_DEPTH {
+            let ex = self.get_execute_data(ex_addr)?;
+            if ex.func == 0 {
+                break;
             }
-            ex_addr = ex.prev_execute_data as usize;
+            args.push(self.read_frame_args(&eg, ex_addr, &ex)?);
+            ex_addr = ex.prev_execute_data;
         }
-        print!("{}", output);
-        Trace{}
+        Ok(args)
+    }
+
+    fn is_executor_globals(&self, addr: usize) -> bool
+    {
+        match self.get_executor_global(addr) {
+            Ok(eg) => self.is_valid_executor_globals(&eg),
+            Err(_) => false,
+        }
+    }
+
+    fn read_request(&self, addr: usize) -> Option<Request>
+    {
+        let sg = &self.layout.sapi_globals;
+        let bytes = copy(&self.source, addr, sg.size).ok()?;
+        let string = |offset| sapi::read_string(&self.source, self.word(&bytes, offset).ok()?);
+        Some(Request {
+            method: string(sg.request_method),
+            uri: string(sg.request_uri),
+            query_string: string(sg.query_string),
+            start_time: field(&bytes, sg.global_request_time).ok()?,
+        })
+    }
+
+    fn find_executor_globals(&self, start: usize, end: usize) -> Option<usize>
+    {
+        let size = self.layout.executor_globals.size;
+        let bytes = copy(&self.source, start, end.checked_sub(start)?).ok()?;
+        (0..=bytes.len().checked_sub(size)?)
+            .step_by(self.layout.word)
+            .find(|offset| match self.parse_executor_globals(&bytes[*offset..*offset + size]) {
+                Ok(eg) => self.is_valid_executor_globals(&eg),
+                Err(_) => false,
+            })
+            .map(|offset| start + offset)
+    }
+}
+
+/// Value of type `R` at `offset` in a copied structure
+fn field<R>(bytes: &[u8], offset: usize) -> Result<R>
+where R: Copy
+{
+    if offset + size_of::<R>() > bytes.len() {
+        return Err(Error::Layout(format!("field at {} past the end of a {} bytes structure", offset, bytes.len())));
+    }
+    Ok(unsafe { ptr::read_unaligned(bytes[offset..].as_ptr() as *const R) })
+}
+
+fn include_kind(opcode: u8, extended_value: u32) -> FrameKind
+{
+    if opcode != ZEND_INCLUDE_OR_EVAL {
+        return FrameKind::Main;
+    }
+
+    match extended_value {
+        ZEND_EVAL => FrameKind::Eval,
+        ZEND_INCLUDE => FrameKind::Include("include"),
+        ZEND_INCLUDE_ONCE => FrameKind::Include("include_once"),
+        ZEND_REQUIRE => FrameKind::Include("require"),
+        ZEND_REQUIRE_ONCE => FrameKind::Include("require_once"),
+        _ => FrameKind::Main,
+    }
+}
+
+/// Quoted and escaped string, `...` appended when only a prefix was read
+fn format_string(bytes: &[u8], len: usize) -> String
+{
+    let mut quoted = format!("'{}", String::from_utf8_lossy(bytes).escape_debug());
+    if len > bytes.len() {
+        quoted.push_str("...");
     }
+    quoted.push('\'');
+    quoted
 }
 
-fn read_memory<R>(source: &ProcessHandle, addr: usize) -> R
+/// Bytes of the process at `addr`
+fn copy(source: &ProcessHandle, addr: usize, size: usize) -> Result<Vec<u8>>
+{
+    copy_address(addr, size, source).map_err(|e| Error::Memory(addr, e))
+}
+
+fn read_memory<R>(source: &ProcessHandle, addr: usize) -> Result<R>
 where R: Copy
 {
-    let size = size_of::<R>();
-    let bytes = copy_address(addr, size, source).unwrap();
-    let bytes_ptr: *mut R = unsafe { transmute(bytes.as_ptr()) };
-    unsafe { *bytes_ptr }
+    let bytes = copy(source, addr, size_of::<R>())?;
+    Ok(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const R) })
 }
 
-fn read_cstr(source: &ProcessHandle, addr: usize) -> String
+fn read_cstr(source: &ProcessHandle, addr: usize) -> Result<String>
 {
     let mut result = String::new();
     let mut i = 0;
-    loop {
-        let c = copy_address(addr + i, 1, source).unwrap()[0];
+    while i < MAX_STRING {
+        let c = copy(source, addr + i, 1)?[0];
         if c == 0 {
-            return result;
+            return Ok(result);
         } else {
             result.push(c as char);
-            i = i + 1;
+            i += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// The walker reading PHP structures laid out in the memory of the test
+/// itself
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests {
+    use super::*;
+    use crate::layout::Abi;
+    use read_process_memory::{Pid, TryIntoProcessHandle};
+
+    /// Builds the structures of a PHP version in leaked buffers, strings
+    /// and generators only for PHP 7
+    struct Memory {
+        version: &'static str,
+        layout: Layout,
+    }
+
+    impl Memory {
+        fn new(version: &'static str) -> Memory
+        {
+            Memory { version, layout: Layout::for_version(version, Abi::Lp64).unwrap() }
+        }
+
+        fn walker(&self) -> Walker
+        {
+            let source = (std::process::id() as Pid).try_into_process_handle().unwrap();
+            Walker::new(source, Layout::for_version(self.version, Abi::Lp64).unwrap())
+        }
+
+        fn alloc(&self, size: usize) -> usize
+        {
+            Box::leak(vec![0u8; size].into_boxed_slice()).as_mut_ptr() as usize
+        }
+
+        fn put<T>(&self, addr: usize, value: T)
+        {
+            unsafe { ptr::write_unaligned(addr as *mut T, value) }
+        }
+
+        fn string(&self, value: &str) -> usize
+        {
+            let string = match &self.layout.engine {
+                Engine::Php7(php7) => &php7.string,
+                Engine::Php5(_) => unreachable!(),
+            };
+            let addr = self.alloc(string.val + value.len() + 1);
+            self.put(addr + string.len, value.len());
+            for (i, byte) in value.bytes().enumerate() {
+                self.put(addr + string.val + i, byte);
+            }
+            addr
+        }
+
+        fn class(&self, name: &str) -> usize
+        {
+            let addr = self.alloc(self.layout.class_entry.name + 8);
+            self.put(addr + self.layout.class_entry.name, self.string(name));
+            addr
+        }
+
+        fn function(&self, name: &str) -> usize
+        {
+            let func = &self.layout.function;
+            let addr = self.alloc(func.size);
+            self.put(addr + func.type_, ZEND_USER_FUNCTION);
+            self.put(addr + func.function_name, self.string(name));
+            self.put(addr + func.filename, self.string("/app/index.php"));
+            addr
+        }
+
+        /// Followed by room for arguments
+        fn execute_data(&self, func: usize, prev: usize) -> usize
+        {
+            let ex = &self.layout.execute_data;
+            let addr = self.alloc(ex.size + MAX_ARGS * self.layout.zval.size);
+            self.put(addr + ex.func, func);
+            self.put(addr + ex.prev_execute_data, prev);
+            addr
+        }
+
+        fn generator(&self, ce: usize, execute_data: usize) -> usize
+        {
+            let php7 = match &self.layout.engine {
+                Engine::Php7(php7) => php7,
+                Engine::Php5(_) => unreachable!(),
+            };
+            let addr = self.alloc(php7.generator_node + 2 * self.layout.word);
+            self.put(addr + self.layout.object.ce, ce);
+            self.put(addr + self.layout.generator.execute_data, execute_data);
+            addr
+        }
+
+        /// EG with the objects store holding the objects from handle 1
+        fn executor_globals(&self, current_execute_data: usize, objects: &[usize]) -> usize
+        {
+            let eg = &self.layout.executor_globals;
+            let addr = self.alloc(eg.size);
+            let buckets = self.alloc((objects.len() + 1) * self.layout.word);
+            for (i, object) in objects.iter().enumerate() {
+                self.put(buckets + (i + 1) * self.layout.word, *object);
+            }
+            self.put(addr + eg.current_execute_data, current_execute_data);
+            self.put(addr + eg.object_buckets, buckets);
+            self.put(addr + eg.objects_top, objects.len() as u32 + 1);
+            addr
+        }
+    }
+
+    fn names(frames: &[Frame]) -> Vec<String>
+    {
+        frames.iter().map(Frame::name).collect()
+    }
+
+    #[test]
+    fn coroutines_of_a_scheduler_loop()
+    {
+        let memory = Memory::new("7.3");
+        let generator = memory.class("Generator");
+
+        // main() runs Scheduler::run(), which resumed the producer
+        let main = memory.execute_data(memory.function("main"), 0);
+        let scheduler = memory.execute_data(memory.function("run"), main);
+        let producer = memory.execute_data(memory.function("producer"), scheduler);
+
+        // Suspended ones keep the caller they last ran from, the scheduler
+        // still running or a frame long gone
+        let consumer = memory.execute_data(memory.function("consumer"), scheduler);
+        let gone = memory.execute_data(memory.function("gone"), 0);
+        let worker = memory.execute_data(memory.function("worker"), gone);
+
+        let objects = [
+            memory.generator(generator, producer),
+            memory.generator(generator, consumer),
+            memory.generator(generator, worker),
+            // Finished
+            memory.generator(generator, 0),
+        ];
+        let eg = memory.executor_globals(producer, &objects);
+
+        let coroutines = memory.walker().read_coroutines(eg).unwrap();
+        let coroutines: Vec<(u32, Vec<String>)> =
+            coroutines.iter().map(|coroutine| (coroutine.handle, names(&coroutine.frames))).collect();
+        assert_eq!(coroutines, [(2, vec!["consumer()".to_string()]), (3, vec!["worker()".to_string()])]);
+    }
+
+    fn executor_globals(memory: &Memory, addr: usize) -> ExecutorGlobals
+    {
+        memory.walker().get_executor_global(addr).unwrap()
+    }
+
+    #[test]
+    fn looping_stack_is_cut()
+    {
+        let memory = Memory::new("7.3");
+        let ex = memory.execute_data(memory.function("recurse"), 0);
+        memory.put(ex + memory.layout.execute_data.prev_execute_data, ex);
+        let eg = memory.executor_globals(ex, &[]);
+
+        let walker = memory.walker();
+        assert_eq!(walker.read(eg).unwrap().frames.len(), MAX_DEPTH);
+        assert_eq!(walker.read_args(eg).unwrap().len(), MAX_DEPTH);
+        assert_eq!(walker.read_execute_data_chain(ex).unwrap().len(), MAX_DEPTH);
+    }
+
+    #[test]
+    fn reference_to_itself_is_not_followed()
+    {
+        let memory = Memory::new("7.3");
+        let php7 = match &memory.layout.engine {
+            Engine::Php7(php7) => php7,
+            Engine::Php5(_) => unreachable!(),
+        };
+        let zval = &memory.layout.zval;
+        // A torn reference whose value is a reference to itself
+        let reference = memory.alloc(php7.reference_val + zval.size);
+        memory.put(reference + php7.reference_val, reference);
+        memory.put(reference + php7.reference_val + zval.type_, IS_REFERENCE);
+        let arg = memory.alloc(zval.size);
+        memory.put(arg, reference);
+        memory.put(arg + zval.type_, IS_REFERENCE);
+
+        let walker = memory.walker();
+        let eg = executor_globals(&memory, memory.executor_globals(0, &[]));
+        assert_eq!(walker.format_zval(&eg, arg).unwrap(), "?");
+    }
+
+    #[test]
+    fn garbage_objects_top_is_an_error()
+    {
+        let memory = Memory::new("7.3");
+        let eg = memory.executor_globals(0, &[]);
+        memory.put(eg + memory.layout.executor_globals.objects_top, u32::MAX);
+
+        assert!(matches!(memory.walker().read_coroutines(eg), Err(Error::Layout(_))));
+    }
+
+    #[test]
+    fn garbage_php5_argument_count()
+    {
+        let memory = Memory::new("5.6");
+        let walker = memory.walker();
+        let eg = executor_globals(&memory, memory.executor_globals(0, &[]));
+        let ex_addr = memory.execute_data(0, 0);
+        let arguments = memory.alloc(memory.layout.word);
+        let ex_arguments = match &memory.layout.engine {
+            Engine::Php5(php5) => php5.arguments,
+            Engine::Php7(_) => unreachable!(),
+        };
+        memory.put(ex_addr + ex_arguments, arguments);
+
+        // Below the start of the address space, or too many to read
+        for num_args in [usize::MAX / 4, arguments / memory.layout.word + 1] {
+            memory.put(arguments, num_args);
+            let ex = walker.get_execute_data(ex_addr).unwrap();
+            assert!(matches!(walker.read_frame_args(&eg, ex_addr, &ex), Err(Error::Layout(_))));
+        }
+
+        // Only the first arguments of a long list are read, here null zvals
+        let word = memory.layout.word;
+        let count = MAX_ARGS + 8;
+        let null = memory.alloc(memory.layout.zval.size);
+        let stack = memory.alloc((count + 1) * word);
+        for i in 0..count {
+            memory.put(stack + i * word, null);
         }
+        memory.put(stack + count * word, count);
+        memory.put(ex_addr + ex_arguments, stack + count * word);
+        let ex = walker.get_execute_data(ex_addr).unwrap();
+        assert_eq!(walker.read_frame_args(&eg, ex_addr, &ex).unwrap(), vec!["NULL"; MAX_ARGS]);
     }
 }
@@ -0,0 +1,84 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// A line of `/proc/<pid>/maps`
+pub struct MapEntry {
+    pub start: usize,
+    pub end: usize,
+    pub perms: String,
+    pub offset: u64,
+    pub inode: u64,
+    /// Path as seen from the mount namespace of the process
+    pub path: Option<String>,
+}
+
+impl MapEntry {
+    pub fn is_executable(&self) -> bool
+    {
+        self.perms.contains('x')
+    }
+}
+
+/// Whether the process still exists
+pub fn is_alive<Pid: Display>(pid: Pid) -> bool
+{
+    Path::new(&format!("/proc/{}", pid)).exists()
+}
+
+pub fn read_maps<Pid: Display>(pid: Pid) -> io::Result<Vec<MapEntry>>
+{
+    let maps = fs::read_to_string(format!("/proc/{}/maps", pid))?;
+    Ok(maps.lines().filter_map(parse_map_entry).collect())
+}
+
+/// `start-end perms offset dev inode path`, the path may contain spaces
+fn parse_map_entry(line: &str) -> Option<MapEntry>
+{
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let perms = fields.next()?.to_string();
+    let offset = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let _dev = fields.next()?;
+    let inode = fields.next()?.parse().ok()?;
+    let path = fields.next().map(str::trim).filter(|path| path.starts_with('/')).map(str::to_string);
+
+    Some(MapEntry {
+        start: usize::from_str_radix(start, 16).ok()?,
+        end: usize::from_str_radix(end, 16).ok()?,
+        perms,
+        offset,
+        inode,
+        path,
+    })
+}
+
+/// Contents of a file mapped by the process. Paths in the maps are relative
+/// to the root of the process, which differs from ours inside a container,
+/// so it is opened through `/proc/<pid>/root`, or the `map_files` link when
+/// the file was deleted or replaced since.
+pub fn read_mapped<Pid: Display>(pid: Pid, entry: &MapEntry) -> io::Result<Vec<u8>>
+{
+    let from_root = match &entry.path {
+        Some(path) => fs::read(format!("/proc/{}/root{}", pid, path)),
+        None => Err(io::Error::from(io::ErrorKind::NotFound)),
+    };
+    from_root.or_else(|_| fs::read(format!("/proc/{}/map_files/{:x}-{:x}", pid, entry.start, entry.end)))
+}
+
+/// Inode of the executable, to find its mappings without comparing paths
+pub fn exe_inode<Pid: Display>(pid: Pid) -> io::Result<u64>
+{
+    Ok(fs::metadata(format!("/proc/{}/exe", pid))?.ino())
+}
+
+/// PID of the process in its own PID namespace, e.g. inside a container.
+/// The same as `pid` when it shares ours.
+pub fn namespace_pid<Pid: Display>(pid: Pid) -> Option<read_process_memory::Pid>
+{
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let nspid = status.lines().find_map(|line| line.strip_prefix("NSpid:"))?;
+    nspid.split_whitespace().last()?.parse().ok()
+}
@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use php_stacktrace::recording::RecordingWriter;
+use php_stacktrace::{get_build_id, is_alive, Tracer};
+
+use crate::{arg_value, create_tracer, fail, parse_value};
+
+pub fn now_micros() -> u64
+{
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros() as u64
+}
+
+fn write_failed(output: &str, e: io::Error) -> !
+{
+    fail(format!("cannot write {}: {}", output, e))
+}
+
+/// Sample processes at a fixed rate into a recording file
+pub fn run(matches: &ArgMatches)
+{
+    let version = matches.value_of("PHP Version");
+    let rate: u32 = arg_value(matches, "rate");
+    let duration = matches.value_of("duration").map(|seconds| Duration::from_secs(parse_value("duration", seconds)));
+    let output = matches.value_of("output").unwrap();
+
+    let mut tracers: Vec<Tracer> = matches
+        .values_of("PID")
+        .unwrap()
+        .map(|pid| create_tracer(parse_value("PID", pid), version).unwrap_or_else(|e| fail(e)))
+        .collect();
+
+    let interval = Duration::from_secs(1) / rate;
+    let file = File::create(output).unwrap_or_else(|e| fail(format!("cannot create {}: {}", output, e)));
+    let version = &tracers[0].version;
+    let mut writer = RecordingWriter::new(BufWriter::new(file), version, now_micros(), interval.as_micros() as u64)
+        .unwrap_or_else(|e| write_failed(output, e));
+    for tracer in &tracers {
+        writer.add_process(tracer.pid as u32, get_build_id(tracer.pid).as_deref()).unwrap_or_else(|e| write_failed(output, e));
+    }
+
+    let start = Instant::now();
+    let mut last_flush = Instant::now();
+    let mut samples = 0;
+    let mut failed = 0;
+    let mut last_error = None;
+
+    while duration.is_none_or(|duration| start.elapsed() < duration) {
+        let tick = Instant::now();
+
+        tracers.retain(|tracer| is_alive(tracer.pid));
+        if tracers.is_empty() {
+            break;
+        }
+
+        for tracer in &tracers {
+            match tracer.snapshot() {
+                Ok(trace) => {
+                    writer
+                        .add_trace(start.elapsed().as_micros() as u64, tracer.pid as u32, &trace)
+                        .unwrap_or_else(|e| write_failed(output, e));
+                    samples += 1;
+                }
+                Err(e) => {
+                    failed += 1;
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        // Keep the file readable if the recorder gets killed
+        if last_flush.elapsed() >= Duration::from_secs(1) {
+            writer.flush().unwrap_or_else(|e| write_failed(output, e));
+            last_flush = Instant::now();
+        }
+
+        if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+
+    writer.flush().unwrap_or_else(|e| write_failed(output, e));
+    println!("Recorded {} samples to {}", samples, output);
+    if let Some(e) = last_error {
+        eprintln!("{} samples failed, the last with: {}", failed, e);
+    }
+}
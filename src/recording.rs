@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+use crate::process_reader::Trace;
+
+/// Recording layout: `MAGIC`, format version, a header, then a stream of
+/// records. Integers are LEB128 varints, strings are length prefixed.
+///
+/// Header: PHP version, start time (µs since epoch), sampling interval (µs).
+/// Records:
+///   `RECORD_PROCESS` pid, build-id bytes
+///   `RECORD_STRING`  bytes, its id is the number of strings seen before
+///   `RECORD_SAMPLE`  time delta (µs), pid, depth, string id per frame (leaf first)
+pub const MAGIC: &[u8; 8] = b"PHPSTACK";
+const FORMAT_VERSION: u64 = 1;
+
+const RECORD_PROCESS: u8 = 1;
+const RECORD_STRING: u8 = 2;
+const RECORD_SAMPLE: u8 = 3;
+
+pub struct Process {
+    pub pid: u32,
+    pub build_id: Option<Vec<u8>>,
+}
+
+pub struct Sample {
+    /// Microseconds since the start of the recording
+    pub time: u64,
+    pub pid: u32,
+    /// String ids of the frames, leaf first
+    pub stack: Vec<u32>,
+}
+
+pub struct Recording {
+    pub php_version: String,
+    /// Microseconds since the epoch
+    pub start_time: u64,
+    /// Microseconds between samples of a process
+    pub interval: u64,
+    pub processes: Vec<Process>,
+    pub strings: Vec<String>,
+    pub samples: Vec<Sample>,
+    ids: HashMap<String, u32>,
+}
+
+impl Recording {
+    pub fn new(php_version: &str, start_time: u64, interval: u64) -> Recording
+    {
+        Recording {
+            php_version: php_version.to_string(),
+            start_time,
+            interval,
+            processes: Vec::new(),
+            strings: Vec::new(),
+            samples: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Id of a string, adding it to the string table if it is new
+    pub fn intern(&mut self, string: &str) -> u32
+    {
+        if let Some(id) = self.ids.get(string) {
+            return *id;
+        }
+        let id = self.strings.len() as u32;
+        self.strings.push(string.to_string());
+        self.ids.insert(string.to_string(), id);
+        id
+    }
+
+    /// Add a sample taken `time` µs after the start of the recording
+    pub fn add_trace(&mut self, time: u64, pid: u32, trace: &Trace)
+    {
+        let stack = trace.frames.iter().map(|frame| self.intern(&frame.name())).collect();
+        self.samples.push(Sample { time, pid, stack });
+    }
+
+    /// Frame names of a sample, leaf first. `read` checks that the string
+    /// ids of the samples it reads exist.
+    pub fn frames<'a>(&'a self, sample: &'a Sample) -> impl Iterator<Item = &'a str> + 'a
+    {
+        sample.stack.iter().map(move |id| self.strings[*id as usize].as_str())
+    }
+
+    /// Read a recording. A truncated last record, as left by an interrupted
+    /// recorder, is ignored.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Recording>
+    {
+        Recording::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read<R: Read>(mut input: R) -> io::Result<Recording>
+    {
+        let mut magic = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a php-stacktrace recording"));
+        }
+        let format_version = read_varint(&mut input)?;
+        if format_version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported recording format version {}", format_version),
+            ));
+        }
+
+        let php_version = read_string(&mut input)?;
+        let start_time = read_varint(&mut input)?;
+        let interval = read_varint(&mut input)?;
+        let mut recording = Recording::new(&php_version, start_time, interval);
+
+        let mut time = 0;
+        loop {
+            let mut kind = [0u8; 1];
+            if input.read(&mut kind)? == 0 {
+                break;
+            }
+            let record = match kind[0] {
+                RECORD_PROCESS => read_process(&mut input).map(|process| recording.processes.push(process)),
+                RECORD_STRING => read_string(&mut input).map(|string| {
+                    // Pushed even if repeated, ids count the records
+                    let id = recording.strings.len() as u32;
+                    recording.ids.entry(string.clone()).or_insert(id);
+                    recording.strings.push(string);
+                }),
+                RECORD_SAMPLE => read_sample(&mut input, &mut time).and_then(|sample| {
+                    match sample.stack.iter().find(|id| **id as usize >= recording.strings.len()) {
+                        Some(id) => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown string {}", id))),
+                        None => {
+                            recording.samples.push(sample);
+                            Ok(())
+                        }
+                    }
+                }),
+                kind => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown record {}", kind)));
+                }
+            };
+            match record {
+                Ok(()) => {}
+                Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(recording)
+    }
+}
+
+/// Streams samples to a recording file as they are taken
+pub struct RecordingWriter<W: Write> {
+    out: W,
+    ids: HashMap<String, u32>,
+    last_time: u64,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn new(mut out: W, php_version: &str, start_time: u64, interval: u64) -> io::Result<RecordingWriter<W>>
+    {
+        out.write_all(MAGIC)?;
+        write_varint(&mut out, FORMAT_VERSION)?;
+        write_bytes(&mut out, php_version.as_bytes())?;
+        write_varint(&mut out, start_time)?;
+        write_varint(&mut out, interval)?;
+
+        Ok(RecordingWriter { out, ids: HashMap::new(), last_time: 0 })
+    }
+
+    pub fn add_process(&mut self, pid: u32, build_id: Option<&[u8]>) -> io::Result<()>
+    {
+        self.out.write_all(&[RECORD_PROCESS])?;
+        write_varint(&mut self.out, pid as u64)?;
+        write_bytes(&mut self.out, build_id.unwrap_or(&[]))
+    }
+
+    pub fn add_trace(&mut self, time: u64, pid: u32, trace: &Trace) -> io::Result<()>
+    {
+        let mut stack = Vec::with_capacity(trace.frames.len());
+        for frame in &trace.frames {
+            stack.push(self.intern(&frame.name())?);
+        }
+
+        self.out.write_all(&[RECORD_SAMPLE])?;
+        write_varint(&mut self.out, time - self.last_time)?;
+        write_varint(&mut self.out, pid as u64)?;
+        write_varint(&mut self.out, stack.len() as u64)?;
+        for id in stack {
+            write_varint(&mut self.out, id as u64)?;
+        }
+        self.last_time = time;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()>
+    {
+        self.out.flush()
+    }
+
+    fn intern(&mut self, string: &str) -> io::Result<u32>
+    {
+        if let Some(id) = self.ids.get(string) {
+            return Ok(*id);
+        }
+        let id = self.ids.len() as u32;
+        self.out.write_all(&[RECORD_STRING])?;
+        write_bytes(&mut self.out, string.as_bytes())?;
+        self.ids.insert(string.to_string(), id);
+        Ok(id)
+    }
+}
+
+fn read_process<R: Read>(input: &mut R) -> io::Result<Process>
+{
+    let pid = read_varint(input)? as u32;
+    let build_id = read_bytes(input)?;
+    let build_id = if build_id.is_empty() { None } else { Some(build_id) };
+    Ok(Process { pid, build_id })
+}
+
+fn read_sample<R: Read>(input: &mut R, time: &mut u64) -> io::Result<Sample>
+{
+    *time += read_varint(input)?;
+    let pid = read_varint(input)? as u32;
+    let depth = read_varint(input)?;
+    let stack = (0..depth).map(|_| read_varint(input).map(|id| id as u32)).collect::<io::Result<_>>()?;
+    Ok(Sample { time: *time, pid, stack })
+}
+
+pub fn write_varint<W: Write + ?Sized>(out: &mut W, mut value: u64) -> io::Result<()>
+{
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return out.write_all(&[byte]);
+        }
+        out.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint<R: Read>(input: &mut R) -> io::Result<u64>
+{
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0u8; 1];
+        input.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "varint too long"));
+        }
+    }
+}
+
+fn write_bytes<W: Write>(out: &mut W, bytes: &[u8]) -> io::Result<()>
+{
+    write_varint(out, bytes.len() as u64)?;
+    out.write_all(bytes)
+}
+
+/// The length is not trusted for allocating, a corrupt one would abort
+fn read_bytes<R: Read>(input: &mut R) -> io::Result<Vec<u8>>
+{
+    let len = read_varint(input)?;
+    let mut bytes = Vec::new();
+    input.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn read_string<R: Read>(input: &mut R) -> io::Result<String>
+{
+    String::from_utf8(read_bytes(input)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_recording() -> Vec<u8>
+    {
+        let mut writer = RecordingWriter::new(Vec::new(), "7.3.12", 1_500_000_000_000_000, 10_000).unwrap();
+        writer.add_process(42, Some(&[0xab, 0xcd])).unwrap();
+        writer.add_process(43, None).unwrap();
+        writer.add_trace(0, 42, &Trace::of_functions(&["usleep", "main"])).unwrap();
+        writer.add_trace(10_000, 43, &Trace::of_functions(&[])).unwrap();
+        writer.add_trace(300_000, 42, &Trace::of_functions(&["strlen", "main"])).unwrap();
+        writer.out
+    }
+
+    #[test]
+    fn varint_round_trip()
+    {
+        for value in [0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX as u64, u64::MAX] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value).unwrap();
+            assert_eq!(read_varint(&mut &bytes[..]).unwrap(), value);
+        }
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 300).unwrap();
+        assert_eq!(bytes, [0xac, 0x02]);
+    }
+
+    #[test]
+    fn varint_errors()
+    {
+        let too_long = [0xff; 10];
+        assert_eq!(read_varint(&mut &too_long[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let truncated = [0x80];
+        assert_eq!(read_varint(&mut &truncated[..]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn recording_round_trip()
+    {
+        let recording = Recording::read(&write_recording()[..]).unwrap();
+
+        assert_eq!(recording.php_version, "7.3.12");
+        assert_eq!(recording.start_time, 1_500_000_000_000_000);
+        assert_eq!(recording.interval, 10_000);
+
+        let processes: Vec<(u32, Option<Vec<u8>>)> =
+            recording.processes.iter().map(|process| (process.pid, process.build_id.clone())).collect();
+        assert_eq!(processes, [(42, Some(vec![0xab, 0xcd])), (43, None)]);
+
+        assert_eq!(recording.strings, ["usleep()", "main()", "strlen()"]);
+        let samples: Vec<(u64, u32, Vec<&str>)> = recording
+            .samples
+            .iter()
+            .map(|sample| (sample.time, sample.pid, recording.frames(sample).collect()))
+            .collect();
+        assert_eq!(
+            samples,
+            [
+                (0, 42, vec!["usleep()", "main()"]),
+                (10_000, 43, vec![]),
+                (300_000, 42, vec!["strlen()", "main()"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_last_record_is_ignored()
+    {
+        let bytes = write_recording();
+        let recording = Recording::read(&bytes[..bytes.len() - 1]).unwrap();
+        assert_eq!(recording.samples.len(), 2);
+    }
+
+    #[test]
+    fn decode_errors()
+    {
+        let not_a_recording = b"PHPSTACX\x01";
+        assert_eq!(Recording::read(&not_a_recording[..]).err().unwrap().kind(), io::ErrorKind::InvalidData);
+
+        let mut unknown_string = write_recording();
+        unknown_string.extend([RECORD_SAMPLE, 0, 42, 1, 3]);
+        assert_eq!(Recording::read(&unknown_string[..]).err().unwrap().kind(), io::ErrorKind::InvalidData);
+
+        // A string longer than the file isn't allocated
+        let mut huge_string = write_recording();
+        huge_string.push(RECORD_STRING);
+        write_varint(&mut huge_string, u64::MAX >> 1).unwrap();
+        assert_eq!(Recording::read(&huge_string[..]).unwrap().strings.len(), 3);
+    }
+}
@@ -0,0 +1,31 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use clap::ArgMatches;
+use php_stacktrace::export::write;
+use php_stacktrace::recording::Recording;
+
+use crate::fail;
+
+/// Convert a recording to another format
+pub fn run(matches: &ArgMatches)
+{
+    let input = matches.value_of("FILE").unwrap();
+    let format = matches.value_of("format").unwrap();
+
+    let recording = Recording::open(input).unwrap_or_else(|e| fail(format!("cannot read {}: {}", input, e)));
+
+    let result = match matches.value_of("output") {
+        Some(output) => {
+            let file = File::create(output).unwrap_or_else(|e| fail(format!("cannot create {}: {}", output, e)));
+            let mut out = BufWriter::new(file);
+            write(format, &recording, &mut out).and_then(|_| out.flush())
+        }
+        None => {
+            let stdout = io::stdout();
+            let mut out = stdout.lock();
+            write(format, &recording, &mut out)
+        }
+    };
+    result.unwrap_or_else(|e| fail(format!("cannot write {}: {}", matches.value_of("output").unwrap_or("stdout"), e)));
+}
@@ -0,0 +1,180 @@
+#![allow(non_camel_case_types)]
+
+use std::os::raw::{c_char, c_int, c_uchar, c_void};
+
+use read_process_memory::{copy_address, ProcessHandle};
+
+/// Longest C string read from the request info
+const MAX_STRING: usize = 4096;
+
+/// `sapi_request_info` of 5.6 to 7.x up to `proto_num`
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sapi_request_info {
+    pub request_method: *const c_char,
+    pub query_string: *mut c_char,
+    pub cookie_data: *mut c_char,
+    pub content_length: i64,
+    pub path_translated: *mut c_char,
+    pub request_uri: *mut c_char,
+    pub request_body: *mut c_void,
+    pub content_type: *const c_char,
+    pub headers_only: c_uchar,
+    pub no_headers: c_uchar,
+    pub headers_read: c_uchar,
+    pub post_entry: *mut c_void,
+    pub content_type_dup: *mut c_char,
+    pub auth_user: *mut c_char,
+    pub auth_password: *mut c_char,
+    pub auth_digest: *mut c_char,
+    pub argv0: *mut c_char,
+    pub current_user: *mut c_char,
+    pub current_user_length: c_int,
+    pub argc: c_int,
+    pub argv: *mut *mut c_char,
+    pub proto_num: c_int,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct zend_llist {
+    pub head: *mut c_void,
+    pub tail: *mut c_void,
+    pub count: usize,
+    pub size: usize,
+    pub dtor: *mut c_void,
+    pub persistent: c_uchar,
+    pub traverse_ptr: *mut c_void,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sapi_headers_struct {
+    pub headers: zend_llist,
+    pub http_response_code: c_int,
+    pub send_default_content_type: c_uchar,
+    pub mimetype: *mut c_char,
+    pub http_status_line: *mut c_char,
+}
+
+/// `sapi_globals_struct` up to `global_request_time`
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct sapi_globals_struct {
+    pub server_context: *mut c_void,
+    pub request_info: sapi_request_info,
+    pub sapi_headers: sapi_headers_struct,
+    pub read_post_bytes: i64,
+    pub post_read: c_uchar,
+    pub headers_sent: c_uchar,
+    pub global_stat: libc::stat,
+    pub default_mimetype: *mut c_char,
+    pub default_charset: *mut c_char,
+    pub rfc1867_uploaded_files: *mut c_void,
+    pub post_max_size: i64,
+    pub options: c_int,
+    pub sapi_started: c_uchar,
+    pub global_request_time: f64,
+}
+
+/// The same structs in ILP32 processes, with 8-byte types 4-aligned as on
+/// i686 and `struct stat` as built with `_FILE_OFFSET_BITS=64`
+pub mod ilp32 {
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct sapi_request_info {
+        pub request_method: u32,
+        pub query_string: u32,
+        pub cookie_data: u32,
+        pub content_length: i32,
+        pub path_translated: u32,
+        pub request_uri: u32,
+        pub request_body: u32,
+        pub content_type: u32,
+        pub headers_only: u8,
+        pub no_headers: u8,
+        pub headers_read: u8,
+        pub post_entry: u32,
+        pub content_type_dup: u32,
+        pub auth_user: u32,
+        pub auth_password: u32,
+        pub auth_digest: u32,
+        pub argv0: u32,
+        pub current_user: u32,
+        pub current_user_length: i32,
+        pub argc: i32,
+        pub argv: u32,
+        pub proto_num: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct zend_llist {
+        pub head: u32,
+        pub tail: u32,
+        pub count: u32,
+        pub size: u32,
+        pub dtor: u32,
+        pub persistent: u8,
+        pub traverse_ptr: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct sapi_headers_struct {
+        pub headers: zend_llist,
+        pub http_response_code: i32,
+        pub send_default_content_type: u8,
+        pub mimetype: u32,
+        pub http_status_line: u32,
+    }
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct sapi_globals_struct {
+        pub server_context: u32,
+        pub request_info: sapi_request_info,
+        pub sapi_headers: sapi_headers_struct,
+        pub read_post_bytes: [u32; 2usize],
+        pub post_read: u8,
+        pub headers_sent: u8,
+        /// `struct stat64`
+        pub global_stat: [u32; 24usize],
+        pub default_mimetype: u32,
+        pub default_charset: u32,
+        pub rfc1867_uploaded_files: u32,
+        pub post_max_size: i32,
+        pub options: i32,
+        pub sapi_started: u8,
+        pub global_request_time: [u32; 2usize],
+    }
+}
+
+/// Request a worker is serving, as seen in `SG(request_info)`
+#[derive(Clone, PartialEq)]
+pub struct Request {
+    pub method: Option<String>,
+    pub uri: Option<String>,
+    pub query_string: Option<String>,
+    /// `SG(global_request_time)`, seconds since the epoch. Set when
+    /// `$_SERVER` is populated and cleared when the request ends, 0 otherwise.
+    pub start_time: f64,
+}
+
+/// NUL terminated string, read without stopping the process: the pointer
+/// may already be freed, so errors give `None` instead of panicking
+pub(crate) fn read_string(source: &ProcessHandle, addr: usize) -> Option<String>
+{
+    if addr == 0 {
+        return None;
+    }
+    let mut bytes = Vec::new();
+    while bytes.len() < MAX_STRING {
+        let byte = copy_address(addr + bytes.len(), 1, source).ok()?[0];
+        if byte == 0 {
+            break;
+        }
+        bytes.push(byte);
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ArgMatches;
+use php_stacktrace::export::{self, write_trace_json};
+use php_stacktrace::recording::{Process, Recording};
+use php_stacktrace::{get_build_id, is_alive, namespace_pid, Tracer};
+use read_process_memory::Pid;
+use regex::Regex;
+
+use crate::{arg_value, create_tracer, fail};
+use crate::metrics::{self, Metrics};
+use crate::record::now_micros;
+
+/// Longest profile a request may ask for
+const MAX_PROFILE_SECONDS: u64 = 300;
+
+/// Bytes of request line and headers read, the rest is ignored
+const MAX_REQUEST_HEAD: u64 = 8192;
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: Vec<u8>) -> Response
+    {
+        Response { status: "200 OK", content_type, body }
+    }
+
+    fn error(status: &'static str, message: impl ToString) -> Response
+    {
+        let mut body = message.to_string().into_bytes();
+        body.push(b'\n');
+        Response { status, content_type: "text/plain", body }
+    }
+}
+
+/// One tracer per process, shared by the requests and the metrics sampler
+/// so that they take turns stopping it instead of racing to attach
+pub struct Tracers {
+    version: Option<String>,
+    tracers: Mutex<HashMap<Pid, Arc<Mutex<Tracer>>>>,
+}
+
+impl Tracers {
+    fn new(version: Option<String>) -> Tracers
+    {
+        Tracers { version, tracers: Mutex::new(HashMap::new()) }
+    }
+
+    /// The tracer of a process, created the first time it is asked for
+    pub fn get(&self, pid: Pid) -> php_stacktrace::Result<Arc<Mutex<Tracer>>>
+    {
+        let mut tracers = self.tracers.lock().unwrap();
+        if let Some(tracer) = tracers.get(&pid) {
+            return Ok(Arc::clone(tracer));
+        }
+        let tracer = Arc::new(Mutex::new(create_tracer(pid, self.version.as_deref())?));
+        tracers.insert(pid, Arc::clone(&tracer));
+        Ok(tracer)
+    }
+
+    /// Forget the tracers of processes that are gone
+    pub fn retain(&self, pids: &[Pid])
+    {
+        self.tracers.lock().unwrap().retain(|pid, _| pids.contains(pid));
+    }
+}
+
+/// Settings shared by all requests
+struct Config {
+    tracers: Arc<Tracers>,
+    rate: u32,
+    /// Fed by the background sampler, when enabled
+    metrics: Option<Arc<Mutex<Metrics>>>,
+}
+
+/// Serve stacks and profiles of local PHP processes over HTTP
+pub fn run(matches: &ArgMatches)
+{
+    let listen = matches.value_of("listen").unwrap();
+    let version = matches.value_of("PHP Version").map(str::to_string);
+    let rate = arg_value(matches, "rate");
+    let tracers = Arc::new(Tracers::new(version));
+    let metrics = matches.value_of("metrics").map(|pattern| {
+        let pattern = Regex::new(pattern).unwrap_or_else(|e| fail(format!("invalid pattern {}: {}", pattern, e)));
+        metrics::spawn_sampler(Arc::clone(&tracers), rate, pattern)
+    });
+    let config = Config { tracers, rate, metrics };
+    let config = Arc::new(config);
+
+    let listener = TcpListener::bind(listen).unwrap_or_else(|e| fail(format!("cannot listen on {}: {}", listen, e)));
+    println!("Listening on http://{}", listen);
+
+    for stream in listener.incoming().flatten() {
+        // Profiles take seconds, don't hold other requests back
+        let config = Arc::clone(&config);
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &config);
+        });
+    }
+}
+
+fn handle_connection(stream: TcpStream, config: &Config) -> io::Result<()>
+{
+    // Idle or endless requests would hold their thread forever
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_HEAD));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers, requests have no body
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => {
+            let (path, query) = target.split_once('?').unwrap_or((target, ""));
+            route(path, &parse_query(query), config)
+        }
+        (Some(_), Some(_)) => Response::error("405 Method Not Allowed", "only GET is supported"),
+        _ => Response::error("400 Bad Request", "malformed request"),
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+fn parse_query(query: &str) -> HashMap<&str, &str>
+{
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .collect()
+}
+
+fn route(path: &str, query: &HashMap<&str, &str>, config: &Config) -> Response
+{
+    match path {
+        "/pids" => pids(),
+        "/stack" => stack(query, config),
+        "/profile" => profile(query, config),
+        "/metrics" => match &config.metrics {
+            Some(metrics) => Response::ok("text/plain; version=0.0.4", metrics.lock().unwrap().render().into_bytes()),
+            None => Response::error("404 Not Found", "start with --metrics to enable /metrics"),
+        },
+        _ => Response::error("404 Not Found", "unknown path, use /pids, /stack, /profile or /metrics"),
+    }
+}
+
+fn pid_param(query: &HashMap<&str, &str>) -> Result<Pid, Response>
+{
+    query
+        .get("pid")
+        .and_then(|pid| pid.parse().ok())
+        .ok_or_else(|| Response::error("400 Bad Request", "missing or invalid pid"))
+}
+
+/// Tracer of a PHP process, other processes are never attached to
+fn tracer(pid: Pid, config: &Config) -> Result<Arc<Mutex<Tracer>>, Response>
+{
+    let pids: Vec<Pid> = php_processes().into_iter().map(|(pid, _)| pid).collect();
+    if !pids.contains(&pid) {
+        return Err(Response::error("404 Not Found", format!("{} is not a PHP process", pid)));
+    }
+    config.tracers.retain(&pids);
+    config.tracers.get(pid).map_err(|e| Response::error("500 Internal Server Error", e))
+}
+
+/// `php`, `php7.3`, `php-fpm`, `php-cgi7.3` and so on
+fn is_php_name(name: &str) -> bool
+{
+    match name.strip_prefix("php") {
+        Some(rest) => {
+            let rest = rest.strip_prefix("-fpm").or_else(|| rest.strip_prefix("-cgi")).unwrap_or(rest);
+            rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Local processes running a PHP executable, with the executable path
+pub fn php_processes() -> Vec<(Pid, String)>
+{
+    let mut processes: Vec<(Pid, String)> = fs::read_dir("/proc")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let pid: Pid = entry.ok()?.file_name().to_str()?.parse().ok()?;
+            let exe = fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+            let name = exe.file_name()?.to_string_lossy().to_string();
+            if is_php_name(&name) {
+                Some((pid, exe.to_string_lossy().to_string()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    processes.sort();
+    processes
+}
+
+fn pids() -> Response
+{
+    let entries: Vec<String> = php_processes()
+        .iter()
+        .map(|(pid, exe)| {
+            let ns_pid = namespace_pid(*pid).map_or("null".to_string(), |ns_pid| ns_pid.to_string());
+            format!("{{\"pid\":{},\"ns_pid\":{},\"exe\":{}}}", pid, ns_pid, export::json_string(exe))
+        })
+        .collect();
+    Response::ok("application/json", format!("[{}]\n", entries.join(",")).into_bytes())
+}
+
+/// `GET /stack?pid=&format=text|json`
+fn stack(query: &HashMap<&str, &str>, config: &Config) -> Response
+{
+    let result = pid_param(query).and_then(|pid| tracer(pid, config)).and_then(|tracer| {
+        tracer.lock().unwrap().snapshot().map_err(|e| Response::error("500 Internal Server Error", e))
+    });
+    let trace = match result {
+        Ok(trace) => trace,
+        Err(response) => return response,
+    };
+
+    match query.get("format").copied().unwrap_or("text") {
+        "json" => {
+            let mut body = Vec::new();
+            match write_trace_json(&trace, &mut body) {
+                Ok(()) => {
+                    body.push(b'\n');
+                    Response::ok("application/json", body)
+                }
+                Err(e) => Response::error("500 Internal Server Error", e),
+            }
+        }
+        "text" => Response::ok("text/plain", trace.to_string().into_bytes()),
+        format => Response::error("400 Bad Request", format!("unknown format {}", format)),
+    }
+}
+
+/// `GET /profile?pid=&seconds=&format=text|folded|json|pprof`
+fn profile(query: &HashMap<&str, &str>, config: &Config) -> Response
+{
+    let format = query.get("format").copied().unwrap_or("pprof");
+    let content_type = match format {
+        "pprof" => "application/octet-stream",
+        "json" => "application/json",
+        "text" | "folded" => "text/plain",
+        _ => return Response::error("400 Bad Request", format!("unknown format {}", format)),
+    };
+    let seconds = match query.get("seconds").map(|seconds| seconds.parse::<u64>()) {
+        None => 10,
+        Some(Ok(seconds)) if seconds <= MAX_PROFILE_SECONDS => seconds,
+        Some(_) => return Response::error("400 Bad Request", "invalid seconds"),
+    };
+    let tracer = match pid_param(query).and_then(|pid| tracer(pid, config)) {
+        Ok(tracer) => tracer,
+        Err(response) => return response,
+    };
+
+    let (pid, version) = {
+        let tracer = tracer.lock().unwrap();
+        (tracer.pid, tracer.version.clone())
+    };
+
+    let interval = Duration::from_secs(1) / config.rate;
+    let mut recording = Recording::new(&version, now_micros(), interval.as_micros() as u64);
+    recording.processes.push(Process {
+        pid: pid as u32,
+        build_id: get_build_id(pid),
+    });
+
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(seconds) {
+        let tick = Instant::now();
+        // Locked per sample, other requests for the process interleave
+        let snapshot = tracer.lock().unwrap().snapshot();
+        match snapshot {
+            Ok(trace) => recording.add_trace(start.elapsed().as_micros() as u64, pid as u32, &trace),
+            // Exited, return what was sampled so far
+            Err(_) if !is_alive(pid) => break,
+            Err(_) => {}
+        }
+        if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+
+    let mut body = Vec::new();
+    match export::write(format, &recording, &mut body) {
+        Ok(()) => Response::ok(content_type, body),
+        Err(e) => Response::error("500 Internal Server Error", e),
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+
+use crate::dwarf::StructLayout;
+use crate::export::hex;
+
+/// One `<build-id> <symbol> <offset>` line per symbol found, and a
+/// `<build-id> php-version <version>` line
+const SYMBOLS: &str = "symbols";
+
+/// Key of the PHP version in `SYMBOLS`, which no symbol can be named
+const VERSION: &str = "php-version";
+
+/// One `<build-id> <struct> <member> <offset>` line per member of the
+/// structs read from DWARF, `sizeof` giving the size of the struct and
+/// `-` marking a struct the debug info lacks
+const STRUCTS: &str = "structs";
+
+/// `$XDG_CACHE_HOME/php-stacktrace/<name>`
+fn path(name: &str) -> Option<PathBuf>
+{
+    let dir = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(dir.join("php-stacktrace").join(name))
+}
+
+/// Offset of a symbol in the executable with the build-id, if already found
+pub fn lookup(build_id: &[u8], symbol: &str) -> Option<usize>
+{
+    let cache = fs::read_to_string(path(SYMBOLS)?).ok()?;
+    let key = key(build_id, symbol);
+    cache.lines().find_map(|line| usize::from_str_radix(line.strip_prefix(&key)?, 16).ok())
+}
+
+/// Best effort, a missing cache only makes the lookup slower
+pub fn store(build_id: &[u8], symbol: &str, offset: usize)
+{
+    let key = key(build_id, symbol);
+    update(SYMBOLS, &key, vec![format!("{}{:x}", key, offset)]);
+}
+
+/// Forget an offset that turned out to be wrong
+pub fn remove(build_id: &[u8], symbol: &str)
+{
+    update(SYMBOLS, &key(build_id, symbol), Vec::new());
+}
+
+/// PHP version of the executable with the build-id, if already read
+pub fn lookup_version(build_id: &[u8]) -> Option<String>
+{
+    let cache = fs::read_to_string(path(SYMBOLS)?).ok()?;
+    let key = key(build_id, VERSION);
+    cache.lines().find_map(|line| line.strip_prefix(&key)).map(str::to_string)
+}
+
+/// Best effort, like `store`
+pub fn store_version(build_id: &[u8], version: &str)
+{
+    let key = key(build_id, VERSION);
+    update(SYMBOLS, &key, vec![format!("{}{}", key, version)]);
+}
+
+/// Layouts of the structs in the executable with the build-id, if all of
+/// them were already read
+pub fn lookup_structs(build_id: &[u8], names: &[&str]) -> Option<HashMap<String, StructLayout>>
+{
+    let cache = fs::read_to_string(path(STRUCTS)?).ok()?;
+    let mut structs = HashMap::new();
+    for name in names {
+        let key = key(build_id, name);
+        let mut layout = StructLayout { size: 0, members: HashMap::new() };
+        let mut found = false;
+        for line in cache.lines().filter_map(|line| line.strip_prefix(&key)) {
+            found = true;
+            let (member, offset) = match line.split_once(' ') {
+                Some((member, offset)) => (member, usize::from_str_radix(offset, 16).ok()?),
+                None => continue,
+            };
+            if member == "sizeof" {
+                layout.size = offset;
+            } else {
+                layout.members.insert(member.to_string(), offset);
+            }
+        }
+        if !found {
+            return None;
+        }
+        if !layout.members.is_empty() {
+            structs.insert(name.to_string(), layout);
+        }
+    }
+    Some(structs)
+}
+
+/// Best effort, like `store`
+pub fn store_structs(build_id: &[u8], names: &[&str], structs: &HashMap<String, StructLayout>)
+{
+    let prefix = format!("{} ", hex(build_id));
+    let mut lines = Vec::new();
+    for name in names {
+        let key = key(build_id, name);
+        match structs.get(*name) {
+            Some(layout) => {
+                lines.push(format!("{}sizeof {:x}", key, layout.size));
+                for (member, offset) in &layout.members {
+                    lines.push(format!("{}{} {:x}", key, member, offset));
+                }
+            }
+            None => lines.push(format!("{}-", key)),
+        }
+    }
+    // Every struct of the build-id is replaced
+    update(STRUCTS, &prefix, lines);
+}
+
+/// `<build-id> <symbol> `, the start of the line of a symbol
+fn key(build_id: &[u8], symbol: &str) -> String
+{
+    format!("{} {} ", hex(build_id), symbol)
+}
+
+/// Replace the lines starting with `key`, written to a temporary file
+/// renamed over the cache so that concurrent readers never see it half
+/// written
+fn update(name: &str, key: &str, new_lines: Vec<String>)
+{
+    let path = match path(name) {
+        Some(path) => path,
+        None => return,
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+
+    let cache = fs::read_to_string(&path).unwrap_or_default();
+    let mut lines: Vec<String> = cache
+        .lines()
+        .filter(|line| !line.starts_with(key))
+        .map(str::to_string)
+        .collect();
+    lines.extend(new_lines);
+
+    let tmp = path.with_extension(format!("{}.tmp", process::id()));
+    let written = fs::File::create(&tmp).and_then(|mut file| {
+        for line in &lines {
+            writeln!(file, "{}", line)?;
+        }
+        Ok(())
+    });
+    if written.and_then(|_| fs::rename(&tmp, &path)).is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+}
@@ -0,0 +1,318 @@
+use std::fmt;
+use std::fs;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use read_process_memory::{copy_address, ProcessHandle};
+
+/// Syscall a process is sleeping in, with the files it waits on
+pub struct Blocked {
+    pub syscall: String,
+    pub targets: Vec<String>,
+}
+
+impl fmt::Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "blocked: {}", self.syscall)?;
+        if !self.targets.is_empty() {
+            write!(f, " on {}", self.targets.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+enum FdArgs {
+    None,
+    /// First argument is a file descriptor
+    Fd,
+    /// `struct pollfd *fds, nfds_t nfds`
+    PollFds,
+    /// `int nfds, fd_set *readfds, fd_set *writefds`
+    FdSets,
+}
+
+#[cfg(target_arch = "x86_64")]
+fn syscall_info(nr: u64) -> (&'static str, FdArgs)
+{
+    match nr {
+        0 => ("read", FdArgs::Fd),
+        1 => ("write", FdArgs::Fd),
+        7 => ("poll", FdArgs::PollFds),
+        17 => ("pread64", FdArgs::Fd),
+        18 => ("pwrite64", FdArgs::Fd),
+        19 => ("readv", FdArgs::Fd),
+        20 => ("writev", FdArgs::Fd),
+        23 => ("select", FdArgs::FdSets),
+        34 => ("pause", FdArgs::None),
+        35 => ("nanosleep", FdArgs::None),
+        40 => ("sendfile", FdArgs::Fd),
+        42 => ("connect", FdArgs::Fd),
+        43 => ("accept", FdArgs::Fd),
+        44 => ("sendto", FdArgs::Fd),
+        45 => ("recvfrom", FdArgs::Fd),
+        46 => ("sendmsg", FdArgs::Fd),
+        47 => ("recvmsg", FdArgs::Fd),
+        61 => ("wait4", FdArgs::None),
+        65 => ("semop", FdArgs::None),
+        70 => ("msgrcv", FdArgs::None),
+        73 => ("flock", FdArgs::Fd),
+        74 => ("fsync", FdArgs::Fd),
+        202 => ("futex", FdArgs::None),
+        230 => ("clock_nanosleep", FdArgs::None),
+        232 => ("epoll_wait", FdArgs::Fd),
+        270 => ("pselect6", FdArgs::FdSets),
+        271 => ("ppoll", FdArgs::PollFds),
+        281 => ("epoll_pwait", FdArgs::Fd),
+        288 => ("accept4", FdArgs::Fd),
+        299 => ("recvmmsg", FdArgs::Fd),
+        _ => ("", FdArgs::None),
+    }
+}
+
+/// The generic table of `asm-generic/unistd.h`, which has no `poll`,
+/// `select` or `epoll_wait`
+#[cfg(target_arch = "aarch64")]
+fn syscall_info(nr: u64) -> (&'static str, FdArgs)
+{
+    match nr {
+        22 => ("epoll_pwait", FdArgs::Fd),
+        32 => ("flock", FdArgs::Fd),
+        63 => ("read", FdArgs::Fd),
+        64 => ("write", FdArgs::Fd),
+        65 => ("readv", FdArgs::Fd),
+        66 => ("writev", FdArgs::Fd),
+        67 => ("pread64", FdArgs::Fd),
+        68 => ("pwrite64", FdArgs::Fd),
+        71 => ("sendfile", FdArgs::Fd),
+        72 => ("pselect6", FdArgs::FdSets),
+        73 => ("ppoll", FdArgs::PollFds),
+        82 => ("fsync", FdArgs::Fd),
+        98 => ("futex", FdArgs::None),
+        101 => ("nanosleep", FdArgs::None),
+        115 => ("clock_nanosleep", FdArgs::None),
+        188 => ("msgrcv", FdArgs::None),
+        193 => ("semop", FdArgs::None),
+        202 => ("accept", FdArgs::Fd),
+        203 => ("connect", FdArgs::Fd),
+        206 => ("sendto", FdArgs::Fd),
+        207 => ("recvfrom", FdArgs::Fd),
+        211 => ("sendmsg", FdArgs::Fd),
+        212 => ("recvmsg", FdArgs::Fd),
+        242 => ("accept4", FdArgs::Fd),
+        243 => ("recvmmsg", FdArgs::Fd),
+        260 => ("wait4", FdArgs::None),
+        _ => ("", FdArgs::None),
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn syscall_info(_: u64) -> (&'static str, FdArgs)
+{
+    ("", FdArgs::None)
+}
+
+/// Read `/proc/<pid>/syscall` of a sleeping process. `None` if it is running
+/// or the file can't be read. The syscalls of `compat` (32-bit) processes
+/// have other numbers and are only named by `wchan`.
+pub fn read_blocked<Pid>(pid: Pid, source: &ProcessHandle, compat: bool) -> Option<Blocked>
+where
+    Pid: fmt::Display + Copy,
+{
+    let (nr, args) = parse_syscall(&fs::read_to_string(format!("/proc/{}/syscall", pid)).ok()?)?;
+
+    let (name, fd_args) = if compat { ("", FdArgs::None) } else { syscall_info(nr) };
+    let name = if !name.is_empty() {
+        name.to_string()
+    } else {
+        match fs::read_to_string(format!("/proc/{}/wchan", pid)) {
+            Ok(wchan) if !wchan.is_empty() && wchan != "0" => wchan,
+            _ => format!("syscall {}", nr),
+        }
+    };
+
+    let fds = match fd_args {
+        FdArgs::None => Vec::new(),
+        FdArgs::Fd => vec![*args.first()? as i32],
+        FdArgs::PollFds => read_pollfds(source, *args.first()? as usize, *args.get(1)? as usize),
+        FdArgs::FdSets => {
+            let nfds = *args.first()? as usize;
+            let mut fds = read_fd_set(source, *args.get(1)? as usize, nfds);
+            fds.extend(read_fd_set(source, *args.get(2)? as usize, nfds));
+            fds
+        }
+    };
+
+    let targets = fds.into_iter().map(|fd| describe_fd(pid, fd)).collect();
+    Some(Blocked { syscall: name, targets })
+}
+
+/// Number and arguments of a `/proc/<pid>/syscall` line, `None` for
+/// `running` and for `-1` (blocked outside of a syscall)
+fn parse_syscall(line: &str) -> Option<(u64, Vec<u64>)>
+{
+    let mut fields = line.split_whitespace();
+    let nr: u64 = fields.next()?.parse().ok()?;
+    let args = fields
+        .map(|arg| u64::from_str_radix(arg.strip_prefix("0x")?, 16).ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((nr, args))
+}
+
+/// File descriptors of a `struct pollfd` array
+fn read_pollfds(source: &ProcessHandle, addr: usize, nfds: usize) -> Vec<i32>
+{
+    // struct pollfd { int fd; short events; short revents; }
+    match copy_address(addr, nfds.min(64) * 8, source) {
+        Ok(bytes) => bytes
+            .chunks(8)
+            .map(|pollfd| i32::from_ne_bytes([pollfd[0], pollfd[1], pollfd[2], pollfd[3]]))
+            .filter(|fd| *fd >= 0)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// File descriptors set in an `fd_set` bitmap
+fn read_fd_set(source: &ProcessHandle, addr: usize, nfds: usize) -> Vec<i32>
+{
+    if addr == 0 {
+        return Vec::new();
+    }
+    match copy_address(addr, nfds.div_ceil(8).min(128), source) {
+        Ok(bytes) => (0..nfds.min(bytes.len() * 8))
+            .filter(|fd| bytes[fd / 8] & (1 << (fd % 8)) != 0)
+            .map(|fd| fd as i32)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Target of `/proc/<pid>/fd/<fd>`, with the peer address for TCP sockets
+fn describe_fd<Pid>(pid: Pid, fd: i32) -> String
+where
+    Pid: fmt::Display + Copy,
+{
+    let target = match fs::read_link(format!("/proc/{}/fd/{}", pid, fd)) {
+        Ok(target) => target.to_string_lossy().to_string(),
+        Err(_) => return format!("fd {}", fd),
+    };
+
+    let inode = match target.strip_prefix("socket:[").and_then(|rest| rest.strip_suffix(']')) {
+        Some(inode) => inode,
+        None => return target,
+    };
+
+    match find_tcp_peer(pid, inode) {
+        Some(peer) => format!("{} -> {}", target, peer),
+        None => target,
+    }
+}
+
+/// Remote address of a TCP socket, looked up by inode in `/proc/<pid>/net/tcp{,6}`
+fn find_tcp_peer<Pid>(pid: Pid, inode: &str) -> Option<String>
+where
+    Pid: fmt::Display + Copy,
+{
+    for table in &["tcp", "tcp6"] {
+        let content = match fs::read_to_string(format!("/proc/{}/net/{}", pid, table)) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+
+        for line in content.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() > 9 && fields[9] == inode {
+                return parse_address(fields[2]);
+            }
+        }
+    }
+    None
+}
+
+/// Parse `0500000A:18EB` (or the IPv6 form) into `10.0.0.5:6379`
+fn parse_address(address: &str) -> Option<String>
+{
+    let (ip, port) = address.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    // Addresses are printed as 32-bit words in host byte order
+    let octets: Vec<u8> = (0..ip.len() / 8)
+        .map(|i| u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).map(u32::to_ne_bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?
+        .concat();
+
+    match octets.len() {
+        4 => Some(format!("{}:{}", Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]), port)),
+        16 => {
+            let mut ipv6 = [0u8; 16];
+            ipv6.copy_from_slice(&octets);
+            Some(format!("[{}]:{}", Ipv6Addr::from(ipv6), port))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_process_memory::{Pid, TryIntoProcessHandle};
+
+    /// Syscall number and arguments
+    type Syscall = (u64, Vec<u64>);
+
+    #[test]
+    fn syscall_lines()
+    {
+        let lines: &[(&str, Option<Syscall>)] = &[
+            ("202 0x55d0c8 0x80 0x0 0x0 0x0 0x0 0x7ffd10 0x7f1a20\n", Some((202, vec![0x55d0c8, 0x80, 0, 0, 0, 0, 0x7ffd10, 0x7f1a20]))),
+            ("230 0x1 0x0 0x7ffd10 0x0\n", Some((230, vec![1, 0, 0x7ffd10, 0]))),
+            ("running\n", None),
+            ("-1 0x7ffd10 0x7f1a20\n", None),
+            ("", None),
+            // Malformed
+            ("7 0xzz 0x2\n", None),
+            ("7 12 0x2\n", None),
+        ];
+        for (line, expected) in lines {
+            assert_eq!(parse_syscall(line), *expected, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn tcp_addresses()
+    {
+        let addresses: &[(&str, Option<&str>)] = &[
+            ("0500000A:18EB", Some("10.0.0.5:6379")),
+            ("0100007F:0050", Some("127.0.0.1:80")),
+            ("00000000000000000000000001000000:1F90", Some("[::1]:8080")),
+            ("0000000000000000FFFF00000500000A:0CEA", Some("[::ffff:10.0.0.5]:3306")),
+            ("0500000A", None),
+            ("0500000A:18EBX", None),
+            ("0500000G:0050", None),
+            ("05000A:0050", None),
+        ];
+        for (address, expected) in addresses {
+            assert_eq!(parse_address(address).as_deref(), *expected, "{}", address);
+        }
+    }
+
+    #[test]
+    fn fd_sets()
+    {
+        let source = (std::process::id() as Pid).try_into_process_handle().unwrap();
+        let bitmap: [u8; 3] = [0b0000_1001, 0b0000_0010, 0b1000_0000];
+        let addr = bitmap.as_ptr() as usize;
+
+        let sets: &[(usize, usize, &[i32])] = &[
+            (addr, 24, &[0, 3, 9, 23]),
+            (addr, 10, &[0, 3, 9]),
+            (addr, 4, &[0, 3]),
+            (addr, 0, &[]),
+            (0, 24, &[]),
+        ];
+        for (addr, nfds, expected) in sets {
+            assert_eq!(read_fd_set(&source, *addr, *nfds), *expected, "{} fds", nfds);
+        }
+    }
+}
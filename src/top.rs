@@ -0,0 +1,131 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ArgMatches;
+
+use php_stacktrace::{is_alive, Trace, Tracer};
+
+use crate::{arg_value, create_tracer, fail, parse_value};
+
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+struct Sample {
+    time: Instant,
+    /// Function on top of the stack, `None` when the process is idle
+    leaf: Option<String>,
+    /// Every distinct function on the stack
+    functions: HashSet<String>,
+}
+
+impl Sample {
+    fn new(trace: &Trace, time: Instant) -> Sample
+    {
+        Sample {
+            time,
+            leaf: trace.frames.first().map(|frame| frame.name()),
+            functions: trace.frames.iter().map(|frame| frame.name()).collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counts {
+    leaf: usize,
+    inclusive: usize,
+}
+
+/// Sample processes continuously and redraw a table of the hottest functions
+pub fn run(matches: &ArgMatches)
+{
+    let version = matches.value_of("PHP Version");
+    let rate: u32 = arg_value(matches, "rate");
+    let window = Duration::from_secs(arg_value(matches, "window"));
+    let lines: usize = arg_value(matches, "lines");
+    let by_inclusive = matches.value_of("sort") == Some("incl");
+
+    let mut tracers: Vec<Tracer> = matches
+        .values_of("PID")
+        .unwrap()
+        .map(|pid| create_tracer(parse_value("PID", pid), version).unwrap_or_else(|e| fail(e)))
+        .collect();
+
+    let interval = Duration::from_secs(1) / rate;
+    let mut samples = VecDeque::new();
+    let mut failed = 0;
+    let mut last_draw = Instant::now();
+
+    loop {
+        let tick = Instant::now();
+
+        tracers.retain(|tracer| is_alive(tracer.pid));
+        if tracers.is_empty() {
+            println!("All processes exited, {} samples failed", failed);
+            return;
+        }
+
+        // A process exiting between the check and the read fails the sample
+        for tracer in &tracers {
+            match tracer.snapshot() {
+                Ok(trace) => samples.push_back(Sample::new(&trace, tick)),
+                Err(_) => failed += 1,
+            }
+        }
+        while samples.front().is_some_and(|sample: &Sample| tick - sample.time > window) {
+            samples.pop_front();
+        }
+
+        if last_draw.elapsed() >= REDRAW_INTERVAL {
+            draw(&samples, tracers.len(), failed, window, lines, by_inclusive);
+            last_draw = Instant::now();
+        }
+
+        if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+}
+
+/// `failed` counts the samples that couldn't be read since the start
+fn draw(samples: &VecDeque<Sample>, processes: usize, failed: usize, window: Duration, lines: usize, by_inclusive: bool)
+{
+    let mut counts: HashMap<&str, Counts> = HashMap::new();
+    let mut idle = 0;
+
+    for sample in samples {
+        match &sample.leaf {
+            Some(leaf) => counts.entry(leaf).or_default().leaf += 1,
+            None => idle += 1,
+        }
+        for function in &sample.functions {
+            counts.entry(function).or_default().inclusive += 1;
+        }
+    }
+
+    let mut rows: Vec<(&str, Counts)> = counts.into_iter().collect();
+    if by_inclusive {
+        rows.sort_by_key(|(_, counts)| Reverse((counts.inclusive, counts.leaf)));
+    } else {
+        rows.sort_by_key(|(_, counts)| Reverse((counts.leaf, counts.inclusive)));
+    }
+
+    let total = samples.len().max(1) as f64;
+    let percent = |count: usize| count as f64 * 100.0 / total;
+
+    // Clear the screen and move the cursor home
+    print!("\x1b[2J\x1b[H");
+    println!(
+        "{} processes, {} samples in the last {}s, {:.1}% idle, {} failed",
+        processes,
+        samples.len(),
+        window.as_secs(),
+        percent(idle),
+        failed
+    );
+    println!();
+    println!("{:>7} {:>7}  FUNCTION", "LEAF", "INCL");
+    for (function, counts) in rows.iter().take(lines) {
+        println!("{:>6.1}% {:>6.1}%  {}", percent(counts.leaf), percent(counts.inclusive), function);
+    }
+}
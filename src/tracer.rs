@@ -0,0 +1,135 @@
+use read_process_memory::{Pid, ProcessHandle, TryIntoProcessHandle};
+
+use crate::attach;
+use crate::debuginfo::{
+    get_abi, get_bss_range, get_debug_structs, get_executor_globals_address, get_php_version,
+};
+use crate::error::{Error, Result};
+use crate::layout::{Abi, Layout, DWARF_STRUCTS};
+use crate::native::{self, ModuleCache};
+use crate::process_reader::{ProcessReader, Trace, Walker};
+use crate::sapi::Request;
+use crate::syscall::{self, Blocked};
+
+/// Reader for a version, given as `major.minor` or a full version string.
+/// Struct layouts come from the debug info of the executable when it has
+/// some, which also lets newer releases of PHP 5 and 7 be read.
+fn create_reader(version: &str, abi: Abi, pid: Pid, source: ProcessHandle) -> Result<Box<dyn ProcessReader + Send>>
+{
+    let minor_version: Vec<&str> = version.split('.').take(2).collect();
+    let structs = get_debug_structs(pid, DWARF_STRUCTS);
+    let layout = match Layout::for_version(&minor_version.join("."), abi) {
+        Some(layout) => layout,
+        None if DWARF_STRUCTS.iter().all(|name| structs.contains_key(*name)) => {
+            Layout::for_major(version, abi).ok_or_else(|| Error::Version(version.to_string()))?
+        }
+        None => return Err(Error::Version(version.to_string())),
+    };
+    Ok(Box::new(Walker::new(source, layout.with_dwarf(&structs))))
+}
+
+/// A PHP process whose stack can be sampled repeatedly
+pub struct Tracer {
+    pub pid: Pid,
+    /// PHP version the stack is read as
+    pub version: String,
+    source: ProcessHandle,
+    abi: Abi,
+    addr: usize,
+    reader: Box<dyn ProcessReader + Send>,
+    modules: ModuleCache,
+    /// Also read suspended generators
+    pub coroutines: bool,
+    /// Also unwind the native stack, of 64-bit processes only
+    pub native: bool,
+    /// Also read the arguments of each frame
+    pub args: bool,
+}
+
+impl Tracer {
+    /// Trace a PHP process, detecting its version from the executable
+    pub fn attach(pid: Pid) -> Result<Tracer>
+    {
+        let version = get_php_version(pid)?;
+        Tracer::with_version(pid, &version)
+    }
+
+    /// Trace a PHP process of a known version, e.g. `7.3`
+    pub fn with_version(pid: Pid, version: &str) -> Result<Tracer>
+    {
+        let source = pid.try_into_process_handle()?;
+        let abi = get_abi(pid)?;
+        let reader = create_reader(version, abi, pid, source)?;
+        let addr = match get_executor_globals_address(pid, |addr| reader.is_executor_globals(addr)) {
+            // Stripped everywhere, look for something that looks like it.
+            // Not cached, the guess may be wrong.
+            Err(Error::Symbol(symbol)) => {
+                let (start, end) = get_bss_range(pid)?;
+                reader.find_executor_globals(start, end).ok_or(Error::Symbol(symbol))?
+            }
+            addr => addr?,
+        };
+
+        Ok(Tracer {
+            pid,
+            version: version.to_string(),
+            source,
+            abi,
+            addr,
+            reader,
+            modules: ModuleCache::default(),
+            coroutines: false,
+            native: false,
+            args: false,
+        })
+    }
+
+    /// Request in the `sapi_globals` at `addr`, e.g. from
+    /// `get_sapi_globals_address`, read without stopping the process
+    pub fn read_request(&self, addr: usize) -> Option<Request>
+    {
+        self.reader.read_request(addr)
+    }
+
+    /// Whether the process is running PHP code, read without stopping it
+    pub fn is_executing(&self) -> Result<bool>
+    {
+        self.reader.is_executing(self.addr)
+    }
+
+    /// Stop the process, read its stack and let it continue
+    pub fn snapshot(&self) -> Result<Trace>
+    {
+        // Read before attaching, the interrupt would kick it out of the syscall
+        let blocked = syscall::read_blocked(self.pid, &self.source, self.abi != Abi::Lp64);
+
+        attach::attach(self.pid)?;
+        let trace = self.read_stopped(blocked);
+        attach::detach(self.pid);
+        trace
+    }
+
+    fn read_stopped(&self, blocked: Option<Blocked>) -> Result<Trace>
+    {
+        let regs = if self.native && self.abi == Abi::Lp64 { attach::get_registers(self.pid) } else { None };
+
+        let mut trace = self.reader.read(self.addr)?;
+        if self.coroutines {
+            trace.coroutines = self.reader.read_coroutines(self.addr)?;
+        }
+        if self.args {
+            for (frame, args) in trace.frames.iter_mut().zip(self.reader.read_args(self.addr)?) {
+                frame.args = Some(args);
+            }
+        }
+        if let Some(frame) = trace.frames.first_mut() {
+            if frame.internal {
+                frame.blocked = blocked;
+            }
+        }
+        if let Some(regs) = regs {
+            trace.native = native::unwind(self.pid, &self.source, regs, &self.modules);
+        }
+        Ok(trace)
+    }
+}
@@ -0,0 +1,105 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use read_process_memory::Pid;
+use regex::Regex;
+
+use php_stacktrace::{is_alive, Trace, Tracer};
+
+use crate::{arg_value, create_tracer, fail, parse_value};
+
+/// Whether any frame of the trace matches the pattern
+fn matches_stack(pattern: &Regex, trace: &Trace) -> bool
+{
+    trace.frames.iter().any(|frame| pattern.is_match(&frame.name()))
+}
+
+/// The trace under a line with the time and PID
+fn write_capture(out: &mut dyn Write, pid: Pid, trace: &Trace) -> io::Result<()>
+{
+    let time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    writeln!(out, "# {:.6} pid {}", time.as_secs_f64(), pid)?;
+    writeln!(out, "{}", trace)?;
+    out.flush()
+}
+
+/// Sample processes at a high rate and print the traces, with arguments, of
+/// the samples whose stack contains a function matching a pattern
+pub fn run(matches: &ArgMatches)
+{
+    let version = matches.value_of("PHP Version");
+    let rate: u32 = arg_value(matches, "rate");
+    let pattern = matches.value_of("PATTERN").unwrap();
+    let pattern = Regex::new(pattern).unwrap_or_else(|e| fail(format!("invalid pattern {}: {}", pattern, e)));
+    let max_captures: usize = arg_value(matches, "count");
+    let cooldown = Duration::from_secs_f64(arg_value(matches, "cooldown"));
+    let duration = matches.value_of("duration").map(|seconds| Duration::from_secs(parse_value("duration", seconds)));
+
+    let mut tracers: Vec<Tracer> = matches
+        .values_of("PID")
+        .unwrap()
+        .map(|pid| create_tracer(parse_value("PID", pid), version).unwrap_or_else(|e| fail(e)))
+        .collect();
+
+    let output = matches.value_of("output");
+    let mut out: Box<dyn Write> = match output {
+        Some(output) => Box::new(File::create(output).unwrap_or_else(|e| fail(format!("cannot create {}: {}", output, e)))),
+        None => Box::new(io::stdout()),
+    };
+
+    let interval = Duration::from_secs(1) / rate;
+    let start = Instant::now();
+    let mut captures = 0;
+    let mut last_capture: Option<Instant> = None;
+
+    'sampling: while captures < max_captures && duration.is_none_or(|duration| start.elapsed() < duration) {
+        // Nothing would be printed until the cooldown is over, so don't
+        // stop the processes meanwhile
+        if let Some(remaining) = last_capture.and_then(|last| cooldown.checked_sub(last.elapsed())) {
+            thread::sleep(remaining);
+            continue;
+        }
+
+        let tick = Instant::now();
+
+        tracers.retain(|tracer| is_alive(tracer.pid));
+        if tracers.is_empty() {
+            break;
+        }
+
+        for tracer in &mut tracers {
+            // Arguments are slow to read, so only read them again once a
+            // sample without them matches
+            if !tracer.snapshot().is_ok_and(|trace| matches_stack(&pattern, &trace)) {
+                continue;
+            }
+            tracer.args = true;
+            let trace = tracer.snapshot();
+            tracer.args = false;
+            let trace = match trace {
+                Ok(trace) if matches_stack(&pattern, &trace) => trace,
+                _ => continue,
+            };
+
+            // The reader went away or the disk is full, nothing more can
+            // be captured
+            if let Err(e) = write_capture(&mut out, tracer.pid, &trace) {
+                eprintln!("cannot write {}: {}", output.unwrap_or("stdout"), e);
+                break 'sampling;
+            }
+
+            captures += 1;
+            last_capture = Some(Instant::now());
+            break;
+        }
+
+        if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+
+    eprintln!("Captured {} traces", captures);
+}
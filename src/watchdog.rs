@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use read_process_memory::Pid;
+
+use php_stacktrace::export::{json_string, write_trace_json};
+use php_stacktrace::sapi::Request;
+use php_stacktrace::{get_sapi_globals_address, is_alive, namespace_pid, Trace, Tracer};
+
+use crate::{arg_value, create_tracer, fail, parse_value};
+
+/// How often the list of pool workers is refreshed
+const RESCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A pool worker and the request it was last seen serving
+struct Worker {
+    tracer: Tracer,
+    sapi_addr: usize,
+    /// Current request and when it started, seconds since the epoch
+    request: Option<(Request, f64)>,
+    /// The current request was already logged
+    captured: bool,
+}
+
+impl Worker {
+    fn new(pid: Pid, version: Option<&str>) -> Option<Worker>
+    {
+        let sapi_addr = get_sapi_globals_address(pid).ok()?;
+        Some(Worker {
+            tracer: create_tracer(pid, version).ok()?,
+            sapi_addr,
+            request: None,
+            captured: false,
+        })
+    }
+
+    /// Update the current request, returning how long it has been running
+    fn poll(&mut self, now: f64) -> Option<f64>
+    {
+        let request = self.tracer.read_request(self.sapi_addr)?;
+
+        // `global_request_time` is only set once `$_SERVER` is populated, so
+        // fall back to the executor to tell an idle worker from a busy one
+        if request.start_time == 0.0 && !self.tracer.is_executing().unwrap_or(false) {
+            self.request = None;
+            return None;
+        }
+
+        // The start time tells apart identical requests following each
+        // other. A request first seen before it was set is the same one
+        // until it is set.
+        let is_same = self.request.as_ref().is_some_and(|(current, _)| {
+            if current.start_time != 0.0 {
+                current.start_time == request.start_time
+            } else {
+                current.method == request.method
+                    && current.uri == request.uri
+                    && current.query_string == request.query_string
+            }
+        });
+        if !is_same {
+            self.captured = false;
+        }
+        let start = match &self.request {
+            _ if request.start_time != 0.0 => request.start_time,
+            Some((_, start)) if is_same => *start,
+            _ => now,
+        };
+        self.request = Some((request, start));
+        self.request.as_ref().map(|(_, start)| now - start)
+    }
+}
+
+/// Children of the given processes, the workers of a php-fpm master
+fn find_workers(masters: &[Pid]) -> Vec<Pid>
+{
+    let entries = match fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<Pid>().ok())
+        .filter(|pid| parent_pid(*pid).is_some_and(|ppid| masters.contains(&ppid)))
+        .collect()
+}
+
+fn parent_pid(pid: Pid) -> Option<Pid>
+{
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces, fields after it are fixed
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    fields.get(1)?.parse().ok()
+}
+
+fn now_secs() -> f64
+{
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs_f64()
+}
+
+/// One JSON line per slow request
+fn write_capture<W: Write>(out: &mut W, worker: &Worker, trace: &Trace, elapsed: f64) -> io::Result<()>
+{
+    let (request, start) = worker.request.as_ref().unwrap();
+    let json_option = |value: &Option<String>| value.as_deref().map_or("null".to_string(), json_string);
+
+    write!(
+        out,
+        "{{\"time\":{:.3},\"pid\":{},\"ns_pid\":{},\"method\":{},\"uri\":{},\"query_string\":{},\"start_time\":{:.3},\"elapsed\":{:.3},\"frames\":",
+        now_secs(),
+        worker.tracer.pid,
+        namespace_pid(worker.tracer.pid).map_or("null".to_string(), |ns_pid| ns_pid.to_string()),
+        json_option(&request.method),
+        json_option(&request.uri),
+        json_option(&request.query_string),
+        start,
+        elapsed
+    )?;
+    write_trace_json(trace, out)?;
+    writeln!(out, "}}")?;
+    out.flush()
+}
+
+/// Watch the workers of php-fpm pools and log the stack of any request
+/// running longer than the threshold
+pub fn run(matches: &ArgMatches)
+{
+    let version = matches.value_of("PHP Version");
+    let threshold: f64 = arg_value(matches, "threshold");
+    let interval = Duration::from_millis(arg_value(matches, "interval"));
+    let output = matches.value_of("output").unwrap();
+    let masters: Vec<Pid> = matches.values_of("PID").unwrap().map(|pid| parse_value("PID", pid)).collect();
+
+    let mut out = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(output)
+        .unwrap_or_else(|e| fail(format!("cannot open {}: {}", output, e)));
+
+    // `None` for children that aren't PHP workers, so they are only checked once
+    let mut workers: HashMap<Pid, Option<Worker>> = HashMap::new();
+    let mut last_scan: Option<Instant> = None;
+
+    loop {
+        let tick = Instant::now();
+
+        if last_scan.is_none_or(|last_scan| last_scan.elapsed() >= RESCAN_INTERVAL) {
+            if !masters.iter().any(|pid| is_alive(*pid)) {
+                println!("All pools exited");
+                return;
+            }
+            let pids = find_workers(&masters);
+            workers.retain(|pid, _| pids.contains(pid));
+            for pid in pids {
+                workers.entry(pid).or_insert_with(|| Worker::new(pid, version));
+            }
+            last_scan = Some(Instant::now());
+        }
+
+        let now = now_secs();
+        for worker in workers.values_mut().flatten() {
+            match worker.poll(now) {
+                Some(elapsed) if elapsed >= threshold && !worker.captured => {
+                    // Retried on the next poll if the worker couldn't be read
+                    if let Ok(trace) = worker.tracer.snapshot() {
+                        if let Err(e) = write_capture(&mut out, worker, &trace, elapsed) {
+                            eprintln!("cannot write to {}: {}", output, e);
+                        }
+                        worker.captured = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(remaining) = interval.checked_sub(tick.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+}